@@ -0,0 +1,124 @@
+//! `config!($crate; prefix = "APP_"; "key": Type => default, ...)`
+//!
+//! The expansion is a value of a local unit struct with one accessor method
+//! per key. An accessor reads its environment variable each time it is
+//! called, so the variables are looked up at runtime, not at compile time.
+
+use std::collections::HashSet;
+
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Expr, ExprLit, Ident, Lit, LitStr, Token, Type};
+
+/// A `"key": Type => default` entry.
+struct Setting {
+    key: LitStr,
+    ty: Type,
+    default: Expr,
+}
+
+impl Parse for Setting {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let ty = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let default = input.parse()?;
+        Ok(Setting { key, ty, default })
+    }
+}
+
+pub struct Config {
+    krate: TokenTree,
+    prefix: Option<LitStr>,
+    settings: Vec<Setting>,
+}
+
+impl Parse for Config {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let krate = input.parse()?;
+        input.parse::<Token![;]>()?;
+        let prefix = if input.peek(Ident) {
+            let ident: Ident = input.parse()?;
+            if ident != "prefix" {
+                return Err(syn::Error::new(ident.span(), "expected `prefix` or a key"));
+            }
+            input.parse::<Token![=]>()?;
+            let prefix = input.parse()?;
+            input.parse::<Token![;]>()?;
+            Some(prefix)
+        } else {
+            None
+        };
+        let settings = Punctuated::<Setting, Token![,]>::parse_terminated(input)?;
+        Ok(Config { krate, prefix, settings: settings.into_iter().collect() })
+    }
+}
+
+impl Config {
+    pub fn expand(&self) -> syn::Result<TokenStream> {
+        let krate = &self.krate;
+        let prefix = self.prefix.as_ref().map_or_else(String::new, LitStr::value);
+        let mut seen = HashSet::with_capacity(self.settings.len());
+        let mut accessors = Vec::with_capacity(self.settings.len());
+        let mut inserts = Vec::with_capacity(self.settings.len());
+        for setting in &self.settings {
+            let key = setting.key.value();
+            let method = match syn::parse_str::<Ident>(&key) {
+                Ok(ref ident) if ident != "to_map" => format_ident!("{}", key, span = setting.key.span()),
+                _ => {
+                    let msg = format!("config key `{}` must be an identifier other than `to_map`", key);
+                    return Err(syn::Error::new(setting.key.span(), msg));
+                }
+            };
+            if !seen.insert(key.clone()) {
+                return Err(syn::Error::new(setting.key.span(), format!("duplicate key `{}`", key)));
+            }
+            let var = format!("{}{}", prefix, key.to_uppercase());
+            let ty = &setting.ty;
+            // A string literal converts into the type, like `String`; other
+            // defaults must have the type already.
+            let default = match setting.default {
+                Expr::Lit(ExprLit { lit: Lit::Str(ref lit), .. }) => {
+                    quote!(<#ty as ::std::convert::From<&'static str>>::from(#lit))
+                }
+                ref default => quote!({ let _value: #ty = #default; _value }),
+            };
+            let doc = format!("Return `{}`, from `${}` if it is set.", key, var);
+            accessors.push(quote! {
+                #[doc = #doc]
+                fn #method(&self) -> #ty {
+                    #krate::__config_var(#var, || #default)
+                }
+            });
+            inserts.push(quote! {
+                let _ = _map.insert(#key, ::std::string::ToString::to_string(&self.#method()));
+            });
+        }
+        let len = inserts.len();
+        Ok(quote! {
+            {
+                #[derive(Clone, Copy, Debug)]
+                struct Config;
+
+                #[allow(dead_code)]
+                impl Config {
+                    #(#accessors)*
+
+                    /// Return the current values, formatted as strings.
+                    fn to_map(&self)
+                        -> ::std::collections::HashMap<&'static str, ::std::string::String>
+                    {
+                        let mut _map = ::std::collections::HashMap::with_capacity(#len);
+                        #(#inserts)*
+                        _map
+                    }
+                }
+
+                Config
+            }
+        })
+    }
+}
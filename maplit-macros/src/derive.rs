@@ -0,0 +1,181 @@
+//! `#[derive(ToMap)]` and `#[derive(FromMap)]`
+//!
+//! The generated code names the maplit crate as `::maplit`, so the deriving
+//! crate must depend on it under that name.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Expr, Field, Fields, GenericArgument, LitStr, PathArguments, Type};
+
+/// The options of a field's `#[map(...)]` attributes.
+struct FieldAttrs {
+    rename: Option<LitStr>,
+    skip: bool,
+    /// The value for a missing key, for `FromMap`.
+    default: Option<TokenStream>,
+}
+
+impl FieldAttrs {
+    fn parse(field: &Field) -> syn::Result<Self> {
+        let mut attrs = FieldAttrs { rename: None, skip: false, default: None };
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("map")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    attrs.rename = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    attrs.skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("default") {
+                    attrs.default = Some(if meta.input.peek(syn::Token![=]) {
+                        let default: Expr = meta.value()?.parse()?;
+                        quote!(#default)
+                    } else {
+                        quote!(::std::default::Default::default())
+                    });
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `rename = \"...\"`, `default` or `skip`"))
+                }
+            })?;
+        }
+        Ok(attrs)
+    }
+}
+
+/// A named field with its key in the map.
+struct MapField<'a> {
+    field: &'a Field,
+    key: String,
+    attrs: FieldAttrs,
+}
+
+/// Return the fields of a struct with named fields.
+fn map_fields<'a>(input: &'a DeriveInput, derive: &str) -> syn::Result<Vec<MapField<'a>>> {
+    let fields = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => Some(&fields.named),
+            _ => None,
+        },
+        _ => None,
+    };
+    let fields = fields.ok_or_else(|| {
+        let msg = format!("`{}` can only be derived for structs with named fields", derive);
+        syn::Error::new(input.ident.span(), msg)
+    })?;
+    let mut map_fields = Vec::with_capacity(fields.len());
+    for field in fields {
+        let attrs = FieldAttrs::parse(field)?;
+        let key = match attrs.rename {
+            Some(ref rename) => rename.value(),
+            None => field.ident.as_ref().unwrap().to_string(),
+        };
+        map_fields.push(MapField { field, key, attrs });
+    }
+    Ok(map_fields)
+}
+
+pub fn to_map(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let mut fields = map_fields(input, "ToMap")?;
+    fields.retain(|f| !f.attrs.skip);
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for &MapField { field, .. } in &fields {
+        let ty = &field.ty;
+        where_clause.predicates.push(syn::parse_quote!(#ty: ::std::clone::Clone));
+        where_clause.predicates.push(syn::parse_quote!(::maplit::Value: ::std::convert::From<#ty>));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let len = fields.len();
+    let inserts = fields.iter().map(|&MapField { field, ref key, .. }| {
+        let ident = &field.ident;
+        quote! {
+            let _ = _map.insert(#key, ::maplit::Value::from(::std::clone::Clone::clone(&self.#ident)));
+        }
+    });
+    Ok(quote! {
+        impl #impl_generics ::maplit::ToMap for #name #ty_generics #where_clause {
+            fn to_map(&self) -> ::std::collections::HashMap<&'static str, ::maplit::Value> {
+                let mut _map = ::std::collections::HashMap::with_capacity(#len);
+                #(#inserts)*
+                _map
+            }
+        }
+    })
+}
+
+/// Return `T` if `ty` is `Option<T>`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let segment = match *ty {
+        Type::Path(ref path) if path.qself.is_none() => path.path.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident != "Option" {
+        return None;
+    }
+    match segment.arguments {
+        PathArguments::AngleBracketed(ref args) if args.args.len() == 1 => match args.args[0] {
+            GenericArgument::Type(ref inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+pub fn from_map(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let fields = map_fields(input, "FromMap")?;
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    let mut inits = Vec::with_capacity(fields.len());
+    for &MapField { field, ref key, ref attrs } in &fields {
+        let ident = &field.ident;
+        if attrs.skip {
+            inits.push(quote!(#ident: ::std::default::Default::default()));
+            continue;
+        }
+        let option = option_inner(&field.ty).filter(|_| attrs.default.is_none());
+        let ty = option.unwrap_or(&field.ty);
+        where_clause.predicates.push(syn::parse_quote!(#ty: ::std::str::FromStr));
+        where_clause.predicates.push(syn::parse_quote! {
+            <#ty as ::std::str::FromStr>::Err: ::std::fmt::Display
+        });
+        let parse = quote!(::maplit::__parse_field::<#ty>(#key, _value)?);
+        let init = match (option, attrs.default.as_ref()) {
+            (Some(_), _) => quote! {
+                match ::maplit::FieldMap::field(map, #key) {
+                    ::std::option::Option::Some(_value) => ::std::option::Option::Some(#parse),
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            },
+            (None, Some(default)) => quote! {
+                match ::maplit::FieldMap::field(map, #key) {
+                    ::std::option::Option::Some(_value) => #parse,
+                    ::std::option::Option::None => #default,
+                }
+            },
+            (None, None) => quote! {
+                match ::maplit::FieldMap::field(map, #key) {
+                    ::std::option::Option::Some(_value) => #parse,
+                    ::std::option::Option::None => {
+                        return ::std::result::Result::Err(::maplit::FromMapError::MissingField(#key));
+                    }
+                }
+            },
+        };
+        inits.push(quote!(#ident: #init));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::maplit::FromMap for #name #ty_generics #where_clause {
+            fn from_map<M: ?::std::marker::Sized + ::maplit::FieldMap>(map: &M)
+                -> ::std::result::Result<Self, ::maplit::FromMapError>
+            {
+                ::std::result::Result::Ok(#name {
+                    #(#inits,)*
+                })
+            }
+        }
+    })
+}
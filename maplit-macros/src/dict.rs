@@ -0,0 +1,125 @@
+//! `dict!($crate; key => value, nested { key => value }, ...)`
+
+use std::collections::HashSet;
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{braced, Expr, Ident, LitStr, Token};
+
+use crate::map_literal::on_new_line;
+
+/// A key, written as an identifier or a string literal.
+struct Key {
+    name: String,
+    span: Span,
+}
+
+impl Parse for Key {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            let lit: LitStr = input.parse()?;
+            Ok(Key { name: lit.value(), span: lit.span() })
+        } else if input.peek(Ident) {
+            let ident: Ident = input.parse()?;
+            let name = ident.to_string();
+            let name = name.strip_prefix("r#").unwrap_or(&name).to_string();
+            Ok(Key { name, span: ident.span() })
+        } else {
+            Err(input.error("expected a key, an identifier or a string literal"))
+        }
+    }
+}
+
+enum Entry {
+    Value(Key, Box<Expr>),
+    Nested(Key, Dict),
+}
+
+/// The entries of a map, in braces or at the top level.
+struct Dict {
+    entries: Vec<Entry>,
+}
+
+impl Parse for Dict {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut entries = Vec::new();
+        while !input.is_empty() {
+            let key: Key = input.parse()?;
+            if input.peek(syn::token::Brace) {
+                let content;
+                braced!(content in input);
+                entries.push(Entry::Nested(key, content.parse()?));
+                // A comma after a nested map is optional.
+                if input.peek(Token![,]) {
+                    input.parse::<Token![,]>()?;
+                }
+                continue;
+            }
+            if !input.peek(Token![=>]) {
+                return Err(input.error(format!("expected `=>` or `{{` after key `{}`", key.name)));
+            }
+            input.parse::<Token![=>]>()?;
+            let value = input.parse()?;
+            let new_line = !input.peek(Token![,]) && on_new_line(input, &value);
+            entries.push(Entry::Value(key, Box::new(value)));
+            if input.is_empty() || new_line {
+                continue;
+            }
+            input.parse::<Token![,]>()?;
+        }
+        Ok(Dict { entries })
+    }
+}
+
+impl Dict {
+    fn expand(&self, krate: &TokenTree) -> syn::Result<TokenStream> {
+        let mut seen = HashSet::with_capacity(self.entries.len());
+        let mut inserts = Vec::with_capacity(self.entries.len());
+        for entry in &self.entries {
+            let (key, value) = match *entry {
+                Entry::Value(ref key, ref value) => (key, quote!(#krate::Value::from(#value))),
+                Entry::Nested(ref key, ref dict) => {
+                    let map = dict.expand(krate)?;
+                    (key, quote!(#krate::Value::Map(#map)))
+                }
+            };
+            if !seen.insert(&key.name) {
+                return Err(syn::Error::new(key.span, format!("duplicate key `{}`", key.name)));
+            }
+            let name = &key.name;
+            inserts.push(quote! {
+                let _ = _map.insert(::std::string::String::from(#name), #value);
+            });
+        }
+        let len = inserts.len();
+        Ok(quote! {
+            {
+                let mut _map = ::std::collections::HashMap::<::std::string::String, #krate::Value>
+                    ::with_capacity(#len);
+                #(#inserts)*
+                _map
+            }
+        })
+    }
+}
+
+pub struct DictMacro {
+    krate: TokenTree,
+    dict: Dict,
+}
+
+impl Parse for DictMacro {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let krate = input.parse()?;
+        input.parse::<Token![;]>()?;
+        let dict = input.parse()?;
+        Ok(DictMacro { krate, dict })
+    }
+}
+
+impl DictMacro {
+    pub fn expand(&self) -> syn::Result<TokenStream> {
+        self.dict.expand(&self.krate)
+    }
+}
@@ -0,0 +1,97 @@
+//! `env_map!(prefix = "APP_")` and `env_map!("NAME" => "default", ...)`
+//!
+//! The values are read with `env!` and `option_env!` in the expansion, so
+//! the compiler tracks the variables and rebuilds the crate when they change.
+
+use std::env;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Expr, Ident, LitStr, Token};
+
+use crate::include::str_expr;
+use crate::map_literal::{Kind, MapLiteral};
+
+/// A variable, with the value to use when it is not set.
+pub struct Var {
+    name: LitStr,
+    default: Option<Expr>,
+}
+
+impl Parse for Var {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        let default = if input.peek(Token![=>]) {
+            input.parse::<Token![=>]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(Var { name, default })
+    }
+}
+
+pub enum EnvMap {
+    Prefix(LitStr),
+    Vars(Vec<Var>),
+}
+
+impl Parse for EnvMap {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Ident) {
+            let ident: Ident = input.parse()?;
+            if ident != "prefix" {
+                return Err(syn::Error::new(ident.span(), "expected `prefix` or a variable name"));
+            }
+            input.parse::<Token![=]>()?;
+            let prefix = input.parse()?;
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+            return Ok(EnvMap::Prefix(prefix));
+        }
+        let vars = Punctuated::<Var, Token![,]>::parse_terminated(input)?;
+        Ok(EnvMap::Vars(vars.into_iter().collect()))
+    }
+}
+
+impl EnvMap {
+    pub fn expand(&self) -> TokenStream {
+        let entries = match *self {
+            EnvMap::Prefix(ref prefix) => {
+                let mut names: Vec<String> = env::vars_os()
+                    .filter_map(|(name, _)| name.into_string().ok())
+                    .filter(|name| name.starts_with(&prefix.value()))
+                    .collect();
+                names.sort();
+                names.iter().map(|name| {
+                    let key = str_expr(name, prefix.span());
+                    let value = syn::parse_quote!(::std::env!(#name));
+                    (key, value)
+                }).collect()
+            }
+            EnvMap::Vars(ref vars) => vars.iter().map(|var| {
+                let name = &var.name;
+                let value = match var.default {
+                    None => syn::parse_quote!(::std::env!(#name)),
+                    Some(ref default) => syn::parse_quote! {
+                        match ::std::option_env!(#name) {
+                            ::std::option::Option::Some(_value) => _value,
+                            ::std::option::Option::None => #default,
+                        }
+                    },
+                };
+                (str_expr(&name.value(), name.span()), value)
+            }).collect(),
+        };
+        let map = MapLiteral { entries }.expand(Kind::Hash);
+        quote! {
+            {
+                let _map: ::std::collections::HashMap<&'static str, &'static str> = #map;
+                _map
+            }
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! `exhaustive_map!(Enum; Variant => value, ...)`
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Expr, Ident, Path, Token};
+
+pub struct ExhaustiveMap {
+    enum_path: Path,
+    entries: Vec<(Ident, Expr)>,
+}
+
+struct Entry {
+    variant: Ident,
+    value: Expr,
+}
+
+impl Parse for Entry {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let variant = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let value = input.parse()?;
+        Ok(Entry { variant, value })
+    }
+}
+
+impl Parse for ExhaustiveMap {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let enum_path = input.parse()?;
+        input.parse::<Token![;]>()?;
+        let parsed = Punctuated::<Entry, Token![,]>::parse_terminated(input)?;
+        let mut entries: Vec<(Ident, Expr)> = Vec::with_capacity(parsed.len());
+        for entry in parsed {
+            if entries.iter().any(|e| e.0 == entry.variant) {
+                let msg = format!("duplicate variant `{}`", entry.variant);
+                return Err(syn::Error::new(entry.variant.span(), msg));
+            }
+            entries.push((entry.variant, entry.value));
+        }
+        Ok(ExhaustiveMap { enum_path, entries })
+    }
+}
+
+impl ExhaustiveMap {
+    pub fn expand(&self) -> TokenStream {
+        let enum_path = &self.enum_path;
+        let variants = self.entries.iter().map(|e| &e.0);
+        let keys = variants.clone();
+        let values = self.entries.iter().map(|e| &e.1);
+        let len = self.entries.len();
+        // The match is never run; it only makes the compiler check that the
+        // listed variants are all the variants of the enum.
+        quote! {
+            {
+                let _ = |_key: &#enum_path| match *_key {
+                    #(#enum_path::#variants => (),)*
+                };
+                let mut _map = ::std::collections::HashMap::with_capacity(#len);
+                #(
+                    let _ = _map.insert(#enum_path::#keys, #values);
+                )*
+                _map
+            }
+        }
+    }
+}
@@ -0,0 +1,330 @@
+//! `include_map!("path")` and `include_map!(csv "path", key = .., value = ..)`
+//!
+//! The file is read when the macro is expanded, and an `include_bytes!` of
+//! it is added to the expansion so that the compiler rebuilds the crate when
+//! the file changes.
+
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{parenthesized, Expr, ExprLit, ExprUnary, Ident, Lit, LitStr, Token, Type, UnOp};
+
+use crate::map_literal::{Kind, MapLiteral};
+
+/// A column of a CSV or TSV file, with the type to parse its fields as.
+/// Without a type, fields are string literals.
+struct Column {
+    name: LitStr,
+    ty: Option<Type>,
+}
+
+impl Parse for Column {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        let ty = if input.peek(Token![as]) {
+            input.parse::<Token![as]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(Column { name, ty })
+    }
+}
+
+/// The columns to read from a CSV or TSV file.
+struct Table {
+    separator: char,
+    key: Column,
+    values: Vec<Column>,
+    /// Whether the value columns were given in parentheses.
+    tuple: bool,
+}
+
+pub struct IncludeMap {
+    path: LitStr,
+    /// `None` for a file of `key=value` lines.
+    table: Option<Table>,
+}
+
+/// Parse `name = ` for the argument `name`.
+fn parse_arg_name(input: ParseStream, name: &str) -> syn::Result<()> {
+    let ident: Ident = input.parse()?;
+    if ident != name {
+        return Err(syn::Error::new(ident.span(), format!("expected `{}`", name)));
+    }
+    input.parse::<Token![=]>()?;
+    Ok(())
+}
+
+impl Parse for IncludeMap {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            let path = input.parse()?;
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+            return Ok(IncludeMap { path, table: None });
+        }
+        let kind: Ident = input.parse()?;
+        let separator = if kind == "csv" {
+            ','
+        } else if kind == "tsv" {
+            '\t'
+        } else {
+            return Err(syn::Error::new(kind.span(), "expected a path, `csv` or `tsv`"));
+        };
+        let path = input.parse()?;
+        input.parse::<Token![,]>()?;
+        parse_arg_name(input, "key")?;
+        let key = input.parse()?;
+        input.parse::<Token![,]>()?;
+        parse_arg_name(input, "value")?;
+        let (values, tuple) = if input.peek(syn::token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            let columns = Punctuated::<Column, Token![,]>::parse_terminated(&content)?;
+            (columns.into_iter().collect(), true)
+        } else {
+            (vec![input.parse()?], false)
+        };
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+        Ok(IncludeMap { path, table: Some(Table { separator, key, values, tuple }) })
+    }
+}
+
+/// Read the file at `path`, relative to the crate root, and return its full
+/// path and contents.
+pub fn read_file(path: &LitStr) -> syn::Result<(String, String)> {
+    let dir = env::var("CARGO_MANIFEST_DIR").map_err(|_| {
+        syn::Error::new(path.span(), "`CARGO_MANIFEST_DIR` is not set; the file can't be found")
+    })?;
+    let full: PathBuf = [dir, path.value()].iter().collect();
+    let full = full.to_string_lossy().into_owned();
+    match fs::read_to_string(&full) {
+        Ok(text) => Ok((full, text)),
+        Err(err) => Err(syn::Error::new(path.span(), format!("couldn't read `{}`: {}", full, err))),
+    }
+}
+
+/// Return a string literal expression.
+pub fn str_expr(value: &str, span: Span) -> Expr {
+    Expr::Lit(ExprLit { attrs: Vec::new(), lit: Lit::Str(LitStr::new(value, span)) })
+}
+
+/// Return an error for line `line` of the file at `path`.
+fn line_error(path: &LitStr, line: usize, msg: &str) -> syn::Error {
+    syn::Error::new(path.span(), format!("{}:{}: {}", path.value(), line, msg))
+}
+
+/// Parse `key=value` lines. Blank lines and lines starting with `#` or `!`
+/// are skipped, and whitespace around keys and values is trimmed.
+fn parse_properties(text: &str, path: &LitStr) -> syn::Result<Vec<(usize, String, String)>> {
+    let mut entries = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('!') {
+            continue;
+        }
+        let eq = line.find('=').ok_or_else(|| line_error(path, i + 1, "expected `key=value`"))?;
+        let key = line[..eq].trim();
+        if key.is_empty() {
+            return Err(line_error(path, i + 1, "empty key"));
+        }
+        entries.push((i + 1, key.to_string(), line[eq + 1..].trim().to_string()));
+    }
+    Ok(entries)
+}
+
+/// Split a line of a CSV or TSV file into its fields.
+///
+/// CSV fields may be quoted with `"`, with `""` for a quote inside them.
+/// Fields that are not quoted are trimmed.
+fn split_fields(line: &str, separator: char) -> Result<Vec<String>, &'static str> {
+    let mut fields = Vec::new();
+    let mut chars = line.chars().peekable();
+    loop {
+        while chars.peek() == Some(&' ') {
+            let _ = chars.next();
+        }
+        let mut field = String::new();
+        if separator == ',' && chars.peek() == Some(&'"') {
+            let _ = chars.next();
+            loop {
+                match chars.next() {
+                    Some('"') if chars.peek() == Some(&'"') => {
+                        let _ = chars.next();
+                        field.push('"');
+                    }
+                    Some('"') => break,
+                    Some(c) => field.push(c),
+                    None => return Err("unterminated quoted field"),
+                }
+            }
+            while chars.peek().is_some_and(|&c| c != separator) {
+                if !chars.next().unwrap().is_whitespace() {
+                    return Err("text after a quoted field");
+                }
+            }
+        } else {
+            while let Some(&c) = chars.peek() {
+                if c == separator {
+                    break;
+                }
+                field.push(c);
+                let _ = chars.next();
+            }
+            field = field.trim().to_string();
+        }
+        fields.push(field);
+        if chars.next().is_none() {
+            return Ok(fields);
+        }
+    }
+}
+
+/// Return the expression for a field of `column`.
+fn field_expr(field: &str, column: &Column, span: Span) -> Result<Expr, String> {
+    let ty = match column.ty {
+        None => return Ok(str_expr(field, span)),
+        Some(ref ty) => ty,
+    };
+    let is_literal = match syn::parse_str::<Expr>(field) {
+        Ok(Expr::Lit(ExprLit { lit: Lit::Int(_), .. }))
+        | Ok(Expr::Lit(ExprLit { lit: Lit::Float(_), .. }))
+        | Ok(Expr::Lit(ExprLit { lit: Lit::Bool(_), .. })) => true,
+        Ok(Expr::Unary(ExprUnary { op: UnOp::Neg(_), ref expr, .. })) => {
+            matches!(**expr, Expr::Lit(ExprLit { lit: Lit::Int(_), .. })
+                             | Expr::Lit(ExprLit { lit: Lit::Float(_), .. }))
+        }
+        _ => false,
+    };
+    if !is_literal {
+        return Err(format!("column \"{}\": expected a number or bool, found `{}`",
+                           column.name.value(), field));
+    }
+    let value: Expr = syn::parse_str(field).unwrap();
+    Ok(syn::parse_quote!({ let _v: #ty = #value; _v }))
+}
+
+/// Parse a CSV or TSV file with a header line. Blank lines are skipped.
+fn parse_table(text: &str, path: &LitStr, table: &Table)
+    -> syn::Result<Vec<(usize, String, Expr, Expr)>>
+{
+    let Table { separator, ref key, ref values, tuple } = *table;
+    let span = path.span();
+    let mut lines = text.lines().enumerate().filter(|l| !l.1.trim().is_empty());
+    let header = match lines.next() {
+        Some((i, line)) => split_fields(line, separator).map_err(|e| line_error(path, i + 1, e))?,
+        None => return Err(syn::Error::new(span, "the file has no header line")),
+    };
+    let index_of = |column: &Column| {
+        header.iter().position(|h| *h == column.name.value()).ok_or_else(|| {
+            let msg = format!("no column \"{}\"; the columns are {:?}", column.name.value(), header);
+            syn::Error::new(column.name.span(), msg)
+        })
+    };
+    let key_index = index_of(key)?;
+    let value_indexes = values.iter().map(&index_of).collect::<syn::Result<Vec<_>>>()?;
+
+    let mut entries = Vec::new();
+    for (i, line) in lines {
+        let fields = split_fields(line, separator).map_err(|e| line_error(path, i + 1, e))?;
+        if fields.len() != header.len() {
+            let msg = format!("expected {} fields, found {}", header.len(), fields.len());
+            return Err(line_error(path, i + 1, &msg));
+        }
+        let key_expr = field_expr(&fields[key_index], key, span)
+            .map_err(|e| line_error(path, i + 1, &e))?;
+        let value_exprs = value_indexes.iter().zip(values)
+            .map(|(&v, column)| field_expr(&fields[v], column, span))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| line_error(path, i + 1, &e))?;
+        let value_expr = if tuple {
+            syn::parse_quote!((#(#value_exprs,)*))
+        } else {
+            value_exprs.into_iter().next().unwrap()
+        };
+        entries.push((i + 1, fields[key_index].clone(), key_expr, value_expr));
+    }
+    Ok(entries)
+}
+
+impl IncludeMap {
+    pub fn expand(&self) -> syn::Result<TokenStream> {
+        let (full, text) = read_file(&self.path)?;
+        let span = self.path.span();
+        let rows = match self.table {
+            None => parse_properties(&text, &self.path)?
+                .into_iter()
+                .map(|(line, k, v)| (line, k.clone(), str_expr(&k, span), str_expr(&v, span)))
+                .collect(),
+            Some(ref table) => parse_table(&text, &self.path, table)?,
+        };
+        let mut seen = HashSet::with_capacity(rows.len());
+        for &(line, ref key, _, _) in &rows {
+            if !seen.insert(key) {
+                return Err(line_error(&self.path, line, &format!("duplicate key `{}`", key)));
+            }
+        }
+        let entries = rows.into_iter().map(|(_, _, k, v)| (k, v)).collect();
+        let map = MapLiteral { entries }.expand(Kind::Hash);
+        Ok(quote! {
+            {
+                const _: &[u8] = ::std::include_bytes!(#full);
+                #map
+            }
+        })
+    }
+}
+
+/// `include_set!("path")`: one element per line.
+pub struct IncludeSet {
+    path: LitStr,
+}
+
+impl Parse for IncludeSet {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+        Ok(IncludeSet { path })
+    }
+}
+
+impl IncludeSet {
+    pub fn expand(&self, kind: Kind) -> syn::Result<TokenStream> {
+        let (full, text) = read_file(&self.path)?;
+        let mut elems: Vec<&str> = text.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .collect();
+        elems.sort_unstable();
+        elems.dedup();
+        let new = match kind {
+            Kind::Hash => {
+                let len = elems.len();
+                quote!(::std::collections::HashSet::with_capacity(#len))
+            }
+            Kind::BTree => quote!(::std::collections::BTreeSet::new()),
+        };
+        Ok(quote! {
+            {
+                const _: &[u8] = ::std::include_bytes!(#full);
+                let mut _set = #new;
+                #(
+                    let _ = _set.insert(#elems);
+                )*
+                _set
+            }
+        })
+    }
+}
@@ -0,0 +1,83 @@
+//! Hashing literal keys like `maplit::FnvHasher` does at runtime.
+//!
+//! The bytes of a key are the bytes that the `Hash` impl of its type feeds
+//! to the hasher, with integers in little endian order.
+
+use syn::{Expr, ExprLit, ExprUnary, Lit, LitInt, UnOp};
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET, |hash, &b| (hash ^ u64::from(b)).wrapping_mul(FNV_PRIME))
+}
+
+/// Return the little endian bytes of the suffixed integer literal `lit`,
+/// negated if `negative`.
+fn int_bytes(lit: &LitInt, negative: bool) -> syn::Result<Vec<u8>> {
+    macro_rules! unsigned {
+        ($t:ty) => {
+            if negative {
+                let msg = format!("negative key for `{}`", lit.suffix());
+                return Err(syn::Error::new_spanned(lit, msg));
+            } else {
+                lit.base10_parse::<$t>()?.to_le_bytes().to_vec()
+            }
+        };
+    }
+    macro_rules! signed {
+        ($t:ty) => {{
+            let value = lit.base10_parse::<u128>()?;
+            let value = if negative {
+                if value > (<$t>::MAX as u128) + 1 {
+                    let msg = format!("key is out of range for `{}`", lit.suffix());
+                    return Err(syn::Error::new_spanned(lit, msg));
+                }
+                (value as $t).wrapping_neg()
+            } else {
+                lit.base10_parse::<$t>()?
+            };
+            value.to_le_bytes().to_vec()
+        }};
+    }
+    Ok(match lit.suffix() {
+        "u8" => unsigned!(u8),
+        "u16" => unsigned!(u16),
+        "u32" => unsigned!(u32),
+        "u64" | "usize" => unsigned!(u64),
+        "u128" => unsigned!(u128),
+        "i8" => signed!(i8),
+        "i16" => signed!(i16),
+        "i32" => signed!(i32),
+        "i64" | "isize" => signed!(i64),
+        "i128" => signed!(i128),
+        "" => {
+            let msg = "integer keys need a type suffix, like `1u32`, to be hashed at compile time";
+            return Err(syn::Error::new_spanned(lit, msg));
+        }
+        _ => return Err(syn::Error::new_spanned(lit, "unsupported integer suffix")),
+    })
+}
+
+/// Return the bytes that the `Hash` impl of the literal `key` writes.
+pub fn key_bytes(key: &Expr) -> syn::Result<Vec<u8>> {
+    match *key {
+        Expr::Lit(ExprLit { lit: Lit::Str(ref s), .. }) => {
+            let mut bytes = s.value().into_bytes();
+            bytes.push(0xff);
+            Ok(bytes)
+        }
+        Expr::Lit(ExprLit { lit: Lit::Char(ref c), .. }) => {
+            Ok((c.value() as u32).to_le_bytes().to_vec())
+        }
+        Expr::Lit(ExprLit { lit: Lit::Bool(ref b), .. }) => Ok(vec![b.value as u8]),
+        Expr::Lit(ExprLit { lit: Lit::Int(ref i), .. }) => int_bytes(i, false),
+        Expr::Group(ref g) => key_bytes(&g.expr),
+        Expr::Unary(ExprUnary { op: UnOp::Neg(_), ref expr, .. }) => match **expr {
+            Expr::Lit(ExprLit { lit: Lit::Int(ref i), .. }) => int_bytes(i, true),
+            _ => Err(syn::Error::new_spanned(key, "expected a literal key")),
+        },
+        _ => Err(syn::Error::new_spanned(
+            key, "keys must be string, char, bool or suffixed integer literals")),
+    }
+}
@@ -0,0 +1,203 @@
+//! Procedural macros for [maplit](https://docs.rs/maplit/).
+//!
+//! Don't depend on this crate directly. Enable the `"proc-macro"` feature of
+//! maplit instead, which re-exports the macros together with their
+//! documentation.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use quote::{quote, quote_spanned};
+
+mod config;
+mod derive;
+mod dict;
+mod env;
+mod exhaustive;
+mod include;
+mod key_hash;
+mod map_literal;
+mod perfect;
+mod prehashed;
+mod query;
+
+/// Turn `err` into `compile_error!` invocations.
+///
+/// Unlike `syn::Error::to_compile_error`, this uses a `::std` path, so it
+/// also works in Rust 2015 crates that don't have `core` in scope.
+fn compile_error(err: syn::Error) -> TokenStream {
+    let errors = err.into_iter().map(|e| {
+        let msg = e.to_string();
+        quote_spanned!(e.span()=> ::std::compile_error!(#msg);)
+    });
+    quote!({ #(#errors)* }).into()
+}
+
+/// Turn `err` into `compile_error!` invocations in item position, for the
+/// output of a derive.
+fn compile_item_error(err: syn::Error) -> TokenStream {
+    let errors = err.into_iter().map(|e| {
+        let msg = e.to_string();
+        quote_spanned!(e.span()=> ::std::compile_error!(#msg);)
+    });
+    quote!(#(#errors)*).into()
+}
+
+/// Create a **HashMap** with a value for every variant of a fieldless enum.
+///
+/// See `maplit::exhaustive_map!` for the documentation.
+#[proc_macro]
+pub fn exhaustive_map(input: TokenStream) -> TokenStream {
+    match syn::parse::<exhaustive::ExhaustiveMap>(input) {
+        Ok(map) => map.expand().into(),
+        Err(err) => compile_error(err),
+    }
+}
+
+/// Create a **HashMap** from a list of key-value pairs, with precise errors.
+///
+/// See `maplit::checked::hashmap!` for the documentation.
+#[proc_macro]
+pub fn hashmap(input: TokenStream) -> TokenStream {
+    match syn::parse::<map_literal::MapLiteral>(input) {
+        Ok(map) => map.expand(map_literal::Kind::Hash).into(),
+        Err(err) => compile_error(err),
+    }
+}
+
+/// Create a **BTreeMap** from a list of key-value pairs, with precise errors.
+///
+/// See `maplit::checked::btreemap!` for the documentation.
+#[proc_macro]
+pub fn btreemap(input: TokenStream) -> TokenStream {
+    match syn::parse::<map_literal::MapLiteral>(input) {
+        Ok(map) => map.expand(map_literal::Kind::BTree).into(),
+        Err(err) => compile_error(err),
+    }
+}
+
+/// Create a **PrehashedMap** with keys hashed at compile time.
+///
+/// See `maplit::prehashed_map!` for the documentation. This takes the path
+/// of the maplit crate before the entries, as in `prehashed_map!(path; ...)`.
+#[proc_macro]
+pub fn prehashed_map(input: TokenStream) -> TokenStream {
+    match syn::parse::<prehashed::PrehashedMap>(input).and_then(|map| map.expand()) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => compile_error(err),
+    }
+}
+
+/// Create a **PerfectMap** with a perfect hash function found at compile
+/// time.
+///
+/// See `maplit::perfectmap!` for the documentation. This takes the path of
+/// the maplit crate before the entries, as in `perfectmap!(path; ...)`.
+#[proc_macro]
+pub fn perfectmap(input: TokenStream) -> TokenStream {
+    match syn::parse::<perfect::PerfectMap>(input).and_then(|map| map.expand()) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => compile_error(err),
+    }
+}
+
+/// Create a **HashMap** from a file of `key=value` lines or a CSV file.
+///
+/// See `maplit::include_map!` for the documentation.
+#[proc_macro]
+pub fn include_map(input: TokenStream) -> TokenStream {
+    match syn::parse::<include::IncludeMap>(input).and_then(|map| map.expand()) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => compile_error(err),
+    }
+}
+
+/// Create a **HashSet** from a file with one element per line.
+///
+/// See `maplit::include_set!` for the documentation.
+#[proc_macro]
+pub fn include_set(input: TokenStream) -> TokenStream {
+    match syn::parse::<include::IncludeSet>(input).and_then(|set| set.expand(map_literal::Kind::Hash)) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => compile_error(err),
+    }
+}
+
+/// Create a **BTreeSet** from a file with one element per line.
+///
+/// See `maplit::btree_include_set!` for the documentation.
+#[proc_macro]
+pub fn btree_include_set(input: TokenStream) -> TokenStream {
+    match syn::parse::<include::IncludeSet>(input).and_then(|set| set.expand(map_literal::Kind::BTree)) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => compile_error(err),
+    }
+}
+
+/// Create a **HashMap** of environment variables read at compile time.
+///
+/// See `maplit::env_map!` for the documentation.
+#[proc_macro]
+pub fn env_map(input: TokenStream) -> TokenStream {
+    match syn::parse::<env::EnvMap>(input) {
+        Ok(map) => map.expand().into(),
+        Err(err) => compile_error(err),
+    }
+}
+
+/// Create a **HashMap** from a query string, decoded at compile time.
+///
+/// See `maplit::querymap!` for the documentation.
+#[proc_macro]
+pub fn querymap(input: TokenStream) -> TokenStream {
+    match syn::parse::<query::QueryMap>(input).and_then(|map| map.expand()) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => compile_error(err),
+    }
+}
+
+/// Create a typed configuration with defaults and environment overrides.
+///
+/// See `maplit::config!` for the documentation. This takes the path of the
+/// maplit crate before the settings, as in `config!(path; ...)`.
+#[proc_macro]
+pub fn config(input: TokenStream) -> TokenStream {
+    match syn::parse::<config::Config>(input).and_then(|config| config.expand()) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => compile_error(err),
+    }
+}
+
+/// Create a **HashMap** of `Value`s, with identifier keys and nested maps.
+///
+/// See `maplit::dict!` for the documentation. This takes the path of the
+/// maplit crate before the entries, as in `dict!(path; ...)`.
+#[proc_macro]
+pub fn dict(input: TokenStream) -> TokenStream {
+    match syn::parse::<dict::DictMacro>(input).and_then(|dict| dict.expand()) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => compile_error(err),
+    }
+}
+
+/// Derive `maplit::ToMap` for a struct with named fields.
+///
+/// See `maplit::ToMap` for the documentation.
+#[proc_macro_derive(ToMap, attributes(map))]
+pub fn derive_to_map(input: TokenStream) -> TokenStream {
+    match syn::parse::<syn::DeriveInput>(input).and_then(|input| derive::to_map(&input)) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => compile_item_error(err),
+    }
+}
+
+/// Derive `maplit::FromMap` for a struct with named fields.
+///
+/// See `maplit::FromMap` for the documentation.
+#[proc_macro_derive(FromMap, attributes(map))]
+pub fn derive_from_map(input: TokenStream) -> TokenStream {
+    match syn::parse::<syn::DeriveInput>(input).and_then(|input| derive::from_map(&input)) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => compile_item_error(err),
+    }
+}
@@ -0,0 +1,255 @@
+//! `hashmap!{key => value, ...}` and `btreemap!{key => value, ...}`
+//!
+//! The entries are parsed by hand rather than with `Punctuated`, so that a
+//! malformed entry is reported at the token where it goes wrong, together
+//! with its position in the list. An entry may end with a line break
+//! instead of a comma.
+
+use std::collections::HashMap;
+
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::{Expr, ExprLit, Ident, Lit, Token};
+
+#[derive(Clone, Copy)]
+pub enum Kind {
+    Hash,
+    BTree,
+}
+
+pub struct MapLiteral {
+    pub entries: Vec<(Expr, Expr)>,
+}
+
+/// Describe the next token of `input` for an error message.
+fn found(input: ParseStream) -> String {
+    match input.cursor().token_tree() {
+        Some((tt, _)) => format!("found `{}`", tt),
+        None => "found end of input".to_string(),
+    }
+}
+
+/// Return `true` if the next token of `input` is on a later line than the
+/// end of `expr`, so that a line break separates them.
+///
+/// The lines are only known with Rust 1.88 or later; before that every
+/// token is on line 0 and a comma is required.
+pub fn on_new_line(input: ParseStream, expr: &Expr) -> bool {
+    let end = match expr.to_token_stream().into_iter().last() {
+        Some(TokenTree::Group(ref group)) => group.span_close().end(),
+        Some(ref tt) => tt.span().end(),
+        None => return false,
+    };
+    match input.cursor().token_tree() {
+        Some((tt, _)) => tt.span().start().line > end.line,
+        None => false,
+    }
+}
+
+/// Parse a value, replacing the `@default` shorthand with
+/// `Default::default()`.
+fn parse_value(input: ParseStream) -> syn::Result<Expr> {
+    if !input.peek(Token![@]) {
+        return input.parse();
+    }
+    input.parse::<Token![@]>()?;
+    let ident: Ident = input.parse()?;
+    if ident != "default" {
+        return Err(syn::Error::new(ident.span(), "expected `@default` or a value expression"));
+    }
+    Ok(Expr::Verbatim(quote_spanned!(ident.span()=> ::std::default::Default::default())))
+}
+
+/// Return the kind of literal that `expr` is, for an error message.
+fn literal_kind(expr: &Expr) -> Option<&'static str> {
+    let lit = match *expr {
+        Expr::Lit(ExprLit { ref lit, .. }) => lit,
+        _ => return None,
+    };
+    Some(match *lit {
+        Lit::Str(_) => "string",
+        Lit::ByteStr(_) => "byte string",
+        Lit::Byte(_) => "byte",
+        Lit::Char(_) => "char",
+        Lit::Int(_) => "integer",
+        Lit::Float(_) => "float",
+        Lit::Bool(_) => "bool",
+        _ => return None,
+    })
+}
+
+/// The number of literals of each kind among the keys or the values.
+#[derive(Default)]
+struct KindCounts {
+    counts: HashMap<&'static str, usize>,
+    total: usize,
+}
+
+impl KindCounts {
+    fn add(&mut self, kind: Option<&'static str>) {
+        if let Some(kind) = kind {
+            *self.counts.entry(kind).or_insert(0) += 1;
+            self.total += 1;
+        }
+    }
+
+    /// Return `true` if all literals but one of another kind are `kind`,
+    /// and there are at least two of them.
+    fn all_others_are(&self, kind: &str) -> bool {
+        let count = self.counts.get(kind).cloned().unwrap_or(0);
+        count >= 2 && count + 1 == self.total
+    }
+}
+
+/// Report an entry that has the literal kinds of the other values and keys,
+/// in reverse.
+fn check_swapped(entries: &[(Expr, Expr)]) -> syn::Result<()> {
+    let mut keys = KindCounts::default();
+    let mut values = KindCounts::default();
+    for (key, value) in entries {
+        keys.add(literal_kind(key));
+        values.add(literal_kind(value));
+    }
+    for (i, (key, value)) in entries.iter().enumerate() {
+        let (key_kind, value_kind) = match (literal_kind(key), literal_kind(value)) {
+            (Some(k), Some(v)) if k != v => (k, v),
+            _ => continue,
+        };
+        if keys.all_others_are(value_kind) && values.all_others_are(key_kind) {
+            let msg = format!("keys and values swapped in entry {}? the other keys are {} \
+                               literals and the other values are {} literals",
+                              i + 1, value_kind, key_kind);
+            return Err(syn::Error::new_spanned(key, msg));
+        }
+    }
+    Ok(())
+}
+
+impl Parse for MapLiteral {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![@]) {
+            return Err(input.error(
+                "modifiers like `@rc;` are only supported by the `macro_rules!` macros; \
+                 use `maplit::hashmap!` or `maplit::btreemap!` for them"));
+        }
+        let mut entries = Vec::new();
+        while !input.is_empty() {
+            let n = entries.len() + 1;
+            if input.peek(Token![=>]) || input.peek(Token![,]) {
+                let msg = format!("expected key expression in entry {}, {}", n, found(input));
+                return Err(input.error(msg));
+            }
+            let key: Expr = input.parse()?;
+            let arrow = if input.peek(Token![=>]) {
+                input.parse::<Token![=>]>()?
+            } else if input.peek(Token![:]) {
+                let msg = format!("expected `=>` after key in entry {}, found `:`; \
+                                   keys and values are separated by `=>`", n);
+                return Err(input.error(msg));
+            } else if input.is_empty() {
+                let msg = format!("missing `=>` after key in entry {}", n);
+                return Err(syn::Error::new_spanned(&key, msg));
+            } else {
+                let msg = format!("missing `=>` after key in entry {}, {}", n, found(input));
+                return Err(input.error(msg));
+            };
+            if input.is_empty() {
+                let msg = format!("expected value expression after `=>` in entry {}, {}",
+                                  n, found(input));
+                return Err(syn::Error::new(arrow.spans[0], msg));
+            }
+            if input.peek(Token![,]) {
+                let msg = format!("expected value expression in entry {}, {}", n, found(input));
+                return Err(input.error(msg));
+            }
+            let value = parse_value(input)?;
+            entries.push((key, value));
+            if input.is_empty() {
+                break;
+            }
+            if input.peek(Token![=>]) {
+                let msg = format!("second `=>` in entry {}; is a `,` missing before it?", n);
+                return Err(input.error(msg));
+            }
+            if !input.peek(Token![,]) && on_new_line(input, &entries[n - 1].1) {
+                continue;
+            }
+            if !input.peek(Token![,]) {
+                let msg = format!("expected `,` after entry {}, {}", n, found(input));
+                return Err(input.error(msg));
+            }
+            input.parse::<Token![,]>()?;
+        }
+        check_swapped(&entries)?;
+        Ok(MapLiteral { entries })
+    }
+}
+
+/// The entries are inserted in blocks of at most this many.
+const CHUNK_LEN: usize = 256;
+
+impl MapLiteral {
+    pub fn expand(&self, kind: Kind) -> TokenStream {
+        let new = match kind {
+            Kind::Hash => {
+                let len = self.entries.len();
+                quote!(::std::collections::HashMap::with_capacity(#len))
+            }
+            Kind::BTree => quote!(::std::collections::BTreeMap::new()),
+        };
+        // Plain blocks rather than closures, so that `?` and `return` in a
+        // value still apply to the caller.
+        let chunks = self.entries.chunks(CHUNK_LEN).map(|chunk| {
+            let inserts = insert_all(chunk);
+            quote!({ #inserts })
+        });
+        quote! {
+            {
+                let mut _map = #new;
+                #(#chunks)*
+                _map
+            }
+        }
+    }
+}
+
+/// Return one `_map.insert(key, value)` statement per entry.
+fn insert_all(entries: &[(Expr, Expr)]) -> TokenStream {
+    let keys = entries.iter().map(|e| &e.0);
+    let values = entries.iter().map(|e| &e.1);
+    quote! {
+        #(
+            let _ = _map.insert(#keys, #values);
+        )*
+    }
+}
+
+#[test]
+fn entries_end_at_commas_or_line_breaks() {
+    let map: MapLiteral = syn::parse_str("\"a\" => 1\n\"b\" => 2, \"c\" => 3\n").unwrap();
+    assert_eq!(map.entries.len(), 3);
+    let err = syn::parse_str::<MapLiteral>("\"a\" => 1 \"b\" => 2").err().unwrap();
+    assert_eq!(err.to_string(), "expected `,` after entry 1, found `\"b\"`");
+}
+
+#[test]
+fn line_break_before_call_parens_continues_value() {
+    let err = syn::parse_str::<MapLiteral>("1 => 2\n(3) => 4").err().unwrap();
+    assert_eq!(err.to_string(), "second `=>` in entry 1; is a `,` missing before it?");
+}
+
+#[test]
+fn default_marker() {
+    let map: MapLiteral = syn::parse_str("\"a\" => @default, \"b\" => default").unwrap();
+    let values: Vec<String> = map.entries.iter().map(|e| e.1.to_token_stream().to_string()).collect();
+    assert_eq!(values, [":: std :: default :: Default :: default ()", "default"]);
+    let err = syn::parse_str::<MapLiteral>("\"a\" => @other").err().unwrap();
+    assert_eq!(err.to_string(), "expected `@default` or a value expression");
+}
+
+#[test]
+fn swapped_entry() {
+    let err = syn::parse_str::<MapLiteral>("\"a\" => 1, \"b\" => 2, 3 => \"c\"").err().unwrap();
+    assert!(err.to_string().starts_with("keys and values swapped in entry 3?"));
+}
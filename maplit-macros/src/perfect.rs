@@ -0,0 +1,112 @@
+//! `perfectmap!($crate; key => value, ...)`
+//!
+//! Finds a perfect hash function for the keys with hash-and-displace: the
+//! keys are split into buckets by a first hash, and then, starting with the
+//! largest bucket, each bucket gets the first displacement that seeds a
+//! second hash placing all its keys in free slots.
+
+use proc_macro2::{TokenStream, TokenTree};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::Token;
+
+use crate::key_hash::{fnv1a, key_bytes};
+use crate::map_literal::MapLiteral;
+
+pub struct PerfectMap {
+    krate: TokenTree,
+    map: MapLiteral,
+}
+
+impl Parse for PerfectMap {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let krate = input.parse()?;
+        input.parse::<Token![;]>()?;
+        let map = input.parse()?;
+        Ok(PerfectMap { krate, map })
+    }
+}
+
+/// Average number of keys per bucket.
+const BUCKET_LEN: usize = 4;
+
+/// Give up on a bucket after trying this many displacements.
+const MAX_DISPLACEMENT: u32 = 1 << 20;
+
+/// Hash `bytes` like `FnvHasher` does after `write_u32(seed)`, and mix the
+/// hash like `maplit::PerfectMap` does.
+fn seeded_hash(seed: u32, bytes: &[u8]) -> u64 {
+    let mut seeded = seed.to_le_bytes().to_vec();
+    seeded.extend_from_slice(bytes);
+    let hash = fnv1a(&seeded);
+    let hash = (hash ^ (hash >> 33)).wrapping_mul(0xff51_afd7_ed55_8ccd);
+    hash ^ (hash >> 33)
+}
+
+/// Return the displacement of each bucket, and the entry index for each
+/// slot, or `None` if some bucket can't be placed.
+fn solve(keys: &[Vec<u8>]) -> Option<(Vec<u32>, Vec<usize>)> {
+    let len = keys.len() as u64;
+    let bucket_count = (keys.len() + BUCKET_LEN - 1) / BUCKET_LEN;
+    let mut buckets = vec![Vec::new(); bucket_count];
+    for (i, key) in keys.iter().enumerate() {
+        buckets[(seeded_hash(0, key) % bucket_count as u64) as usize].push(i);
+    }
+    let mut order: Vec<usize> = (0..bucket_count).collect();
+    order.sort_by_key(|&b| ::std::cmp::Reverse(buckets[b].len()));
+
+    let mut displacements = vec![0; bucket_count];
+    let mut slots: Vec<Option<usize>> = vec![None; keys.len()];
+    let mut placed = Vec::new();
+    for b in order {
+        if buckets[b].is_empty() {
+            break;
+        }
+        let found = (1..MAX_DISPLACEMENT).find(|&d| {
+            placed.clear();
+            for &i in &buckets[b] {
+                let slot = (seeded_hash(d, &keys[i]) % len) as usize;
+                if slots[slot].is_some() || placed.contains(&slot) {
+                    return false;
+                }
+                placed.push(slot);
+            }
+            true
+        })?;
+        displacements[b] = found;
+        for (&i, &slot) in buckets[b].iter().zip(&placed) {
+            slots[slot] = Some(i);
+        }
+    }
+    Some((displacements, slots.into_iter().map(|s| s.unwrap()).collect()))
+}
+
+impl PerfectMap {
+    pub fn expand(&self) -> syn::Result<TokenStream> {
+        let krate = &self.krate;
+        let entries = &self.map.entries;
+        let mut keys: Vec<Vec<u8>> = Vec::with_capacity(entries.len());
+        for (key, _) in entries {
+            let bytes = key_bytes(key)?;
+            if keys.contains(&bytes) {
+                return Err(syn::Error::new_spanned(key, "duplicate key"));
+            }
+            keys.push(bytes);
+        }
+        let (displacements, slots) = match solve(&keys) {
+            Some(solution) => solution,
+            None => {
+                let msg = "could not find a perfect hash function for these keys";
+                return Err(syn::Error::new(proc_macro2::Span::call_site(), msg));
+            }
+        };
+        let table = slots.iter().map(|&i| {
+            let key = &entries[i].0;
+            let value = &entries[i].1;
+            quote!((#key, #value))
+        });
+        Ok(quote! {
+            #krate::PerfectMap::__new(&[#(#displacements),*], &[#(#table),*])
+        })
+    }
+}
@@ -0,0 +1,48 @@
+//! `prehashed_map!($crate; key => value, ...)`
+//!
+//! The keys are hashed here, and the entries inserted with their hashes.
+
+use proc_macro2::{TokenStream, TokenTree};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::Token;
+
+use crate::key_hash::{fnv1a, key_bytes};
+use crate::map_literal::MapLiteral;
+
+pub struct PrehashedMap {
+    krate: TokenTree,
+    map: MapLiteral,
+}
+
+impl Parse for PrehashedMap {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let krate = input.parse()?;
+        input.parse::<Token![;]>()?;
+        let map = input.parse()?;
+        Ok(PrehashedMap { krate, map })
+    }
+}
+
+impl PrehashedMap {
+    pub fn expand(&self) -> syn::Result<TokenStream> {
+        let krate = &self.krate;
+        let entries = &self.map.entries;
+        let len = entries.len();
+        let mut inserts = Vec::with_capacity(len);
+        for (key, value) in entries {
+            let hash = fnv1a(&key_bytes(key)?);
+            inserts.push(quote! {
+                #krate::__insert_prehashed(&mut _map, #hash, #key, #value);
+            });
+        }
+        Ok(quote! {
+            {
+                let mut _map = #krate::PrehashedMap::with_capacity_and_hasher(
+                    #len, ::std::default::Default::default());
+                #(#inserts)*
+                _map
+            }
+        })
+    }
+}
@@ -0,0 +1,112 @@
+//! `querymap!("a=1&b=2")` and `querymap!(@single; "a=1&b=2")`
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, LitStr, Token};
+
+pub struct QueryMap {
+    query: LitStr,
+    /// Whether each key maps to a single `String` instead of a `Vec`.
+    single: bool,
+}
+
+impl Parse for QueryMap {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let single = if input.peek(Token![@]) {
+            input.parse::<Token![@]>()?;
+            let ident: Ident = input.parse()?;
+            if ident != "single" {
+                return Err(syn::Error::new(ident.span(), "expected `@single;`"));
+            }
+            input.parse::<Token![;]>()?;
+            true
+        } else {
+            false
+        };
+        let query = input.parse()?;
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+        Ok(QueryMap { query, single })
+    }
+}
+
+/// Decode `+` and `%XX` escapes in a key or value.
+fn decode(text: &str) -> Result<String, String> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        rest = tail;
+        match b {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let hex = rest.get(..2)
+                    .filter(|h| h.iter().all(u8::is_ascii_hexdigit))
+                    .and_then(|h| ::std::str::from_utf8(h).ok())
+                    .and_then(|h| u8::from_str_radix(h, 16).ok())
+                    .ok_or_else(|| format!("invalid percent escape in `{}`", text))?;
+                bytes.push(hex);
+                rest = &rest[2..];
+            }
+            _ => bytes.push(b),
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| format!("`{}` does not decode to UTF-8", text))
+}
+
+/// Split a query string into decoded key-value pairs, grouping the values
+/// of each key in order.
+fn parse_query(query: &str) -> Result<Vec<(String, Vec<String>)>, String> {
+    let query = query.strip_prefix('?').unwrap_or(query);
+    let mut pairs: Vec<(String, Vec<String>)> = Vec::new();
+    for pair in query.split('&').filter(|p| !p.is_empty()) {
+        let (key, value) = match pair.find('=') {
+            Some(eq) => (&pair[..eq], &pair[eq + 1..]),
+            None => (pair, ""),
+        };
+        let key = decode(key)?;
+        let value = decode(value)?;
+        match pairs.iter().position(|p| p.0 == key) {
+            Some(i) => pairs[i].1.push(value),
+            None => pairs.push((key, vec![value])),
+        }
+    }
+    Ok(pairs)
+}
+
+impl QueryMap {
+    pub fn expand(&self) -> syn::Result<TokenStream> {
+        let error = |msg: String| syn::Error::new(self.query.span(), msg);
+        let pairs = parse_query(&self.query.value()).map_err(error)?;
+        let len = pairs.len();
+        let mut inserts = Vec::with_capacity(len);
+        for (key, values) in pairs {
+            let value = if self.single {
+                if values.len() > 1 {
+                    return Err(error(format!("repeated key `{}`", key)));
+                }
+                let value = &values[0];
+                quote!(::std::string::String::from(#value))
+            } else {
+                quote!(::std::vec![#(::std::string::String::from(#values)),*])
+            };
+            inserts.push(quote! {
+                let _ = _map.insert(::std::string::String::from(#key), #value);
+            });
+        }
+        let value_type = if self.single {
+            quote!(::std::string::String)
+        } else {
+            quote!(::std::vec::Vec<::std::string::String>)
+        };
+        Ok(quote! {
+            {
+                let mut _map: ::std::collections::HashMap<::std::string::String, #value_type> =
+                    ::std::collections::HashMap::with_capacity(#len);
+                #(#inserts)*
+                _map
+            }
+        })
+    }
+}
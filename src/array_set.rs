@@ -0,0 +1,94 @@
+//! A fixed-capacity sorted set backed by an `ArrayVec`.
+
+use std::borrow::Borrow;
+use std::slice;
+
+use arrayvec::ArrayVec;
+
+/// A sorted set of at most `N` elements, stored inline without heap
+/// allocation.
+///
+/// Membership tests use binary search. Created with the
+/// [`arrayset!`](macro.arrayset.html) macro.
+///
+/// Requires crate feature `"arrayvec"`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArraySet<T, const N: usize> {
+    elements: ArrayVec<T, N>,
+}
+
+impl<T: Ord, const N: usize> ArraySet<T, N> {
+    /// Create a new, empty set.
+    pub fn new() -> Self {
+        ArraySet { elements: ArrayVec::new() }
+    }
+
+    /// Return the number of elements.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Return `true` if the set has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Return the capacity of the set, `N`.
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Return `true` if the set contains `value`.
+    pub fn contains<Q>(&self, value: &Q) -> bool
+        where T: Borrow<Q>, Q: ?Sized + Ord
+    {
+        self.elements.binary_search_by(|x| x.borrow().cmp(value)).is_ok()
+    }
+
+    /// Insert `value`, keeping the elements sorted.
+    ///
+    /// Return `true` if the value was not already present.
+    ///
+    /// **Panics** if the value is new and the set is full.
+    pub fn insert(&mut self, value: T) -> bool {
+        match self.elements.binary_search(&value) {
+            Ok(_) => false,
+            Err(i) => {
+                self.elements.insert(i, value);
+                true
+            }
+        }
+    }
+
+    /// Return the elements as a sorted slice.
+    pub fn as_slice(&self) -> &[T] {
+        &self.elements
+    }
+
+    /// Return an iterator of the elements, in sorted order.
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.elements.iter()
+    }
+}
+
+impl<T: Ord, const N: usize> Default for ArraySet<T, N> {
+    fn default() -> Self {
+        ArraySet::new()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a ArraySet<T, N> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.elements.iter()
+    }
+}
+
+impl<T, const N: usize> IntoIterator for ArraySet<T, N> {
+    type Item = T;
+    type IntoIter = arrayvec::IntoIter<T, N>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.elements.into_iter()
+    }
+}
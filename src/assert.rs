@@ -0,0 +1,59 @@
+//! Failure messages for the assertion macros.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Debug, Write};
+
+/// Index entries by the `Debug` form of their key, which sorts them for
+/// output without requiring `Ord`.
+fn by_debug_key<'a, K, V, I>(entries: I) -> BTreeMap<String, &'a V>
+    where I: IntoIterator<Item=(&'a K, &'a V)>, K: Debug + 'a, V: 'a
+{
+    entries.into_iter().map(|(k, v)| (format!("{:?}", k), v)).collect()
+}
+
+/// Describe the differences between two maps, for `assert_map_eq!`.
+#[doc(hidden)]
+pub fn __map_diff_message<'a, K, V, L, R>(left: L, right: R) -> String
+    where L: IntoIterator<Item=(&'a K, &'a V)>,
+          R: IntoIterator<Item=(&'a K, &'a V)>,
+          K: Debug + 'a, V: Debug + PartialEq + 'a
+{
+    let left = by_debug_key(left);
+    let right = by_debug_key(right);
+    let mut msg = String::from("assertion failed: `left == right` (maps differ)");
+    for (key, value) in &left {
+        match right.get(key) {
+            None => { let _ = write!(msg, "\n  only in left: {} => {:?}", key, value); }
+            Some(other) if value != other => {
+                let _ = write!(msg, "\n  different values for {}: left {:?}, right {:?}",
+                               key, value, other);
+            }
+            Some(_) => {}
+        }
+    }
+    for (key, value) in &right {
+        if !left.contains_key(key) {
+            let _ = write!(msg, "\n  only in right: {} => {:?}", key, value);
+        }
+    }
+    msg
+}
+
+/// Describe the differences between two sets, for `assert_set_eq!`.
+#[doc(hidden)]
+pub fn __set_diff_message<'a, T, L, R>(left: L, right: R) -> String
+    where L: IntoIterator<Item=&'a T>, R: IntoIterator<Item=&'a T>, T: Debug + 'a
+{
+    let left: BTreeSet<_> = left.into_iter().map(|x| format!("{:?}", x)).collect();
+    let right: BTreeSet<_> = right.into_iter().map(|x| format!("{:?}", x)).collect();
+    let mut msg = String::from("assertion failed: `left == right` (sets differ)");
+    let only_left: Vec<_> = left.difference(&right).map(|s| &s[..]).collect();
+    let only_right: Vec<_> = right.difference(&left).map(|s| &s[..]).collect();
+    if !only_left.is_empty() {
+        let _ = write!(msg, "\n  only in left: [{}]", only_left.join(", "));
+    }
+    if !only_right.is_empty() {
+        let _ = write!(msg, "\n  only in right: [{}]", only_right.join(", "));
+    }
+    msg
+}
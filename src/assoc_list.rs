@@ -0,0 +1,123 @@
+//! An association list backed by a `SmallVec`.
+
+use std::borrow::Borrow;
+use std::iter::FromIterator;
+use std::slice;
+
+use smallvec::{self, SmallVec};
+
+/// A small association list: a map stored as a list of key-value pairs.
+///
+/// Lookups are a linear scan, which beats hashing for a handful of entries.
+/// Up to eight entries are stored inline, without heap allocation.
+///
+/// Created with the [`alist!`](macro.alist.html) macro or by collecting from
+/// an iterator.
+///
+/// Requires crate feature `"smallvec"`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AssocList<K, V> {
+    entries: SmallVec<[(K, V); 8]>,
+}
+
+impl<K, V> AssocList<K, V> {
+    /// Create a new, empty association list.
+    pub fn new() -> Self {
+        AssocList { entries: SmallVec::new() }
+    }
+
+    /// Return the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return `true` if there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Return a reference to the value for `key`, if present.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+        where K: Borrow<Q>, Q: ?Sized + Eq
+    {
+        self.entries.iter().find(|e| e.0.borrow() == key).map(|e| &e.1)
+    }
+
+    /// Return a mutable reference to the value for `key`, if present.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+        where K: Borrow<Q>, Q: ?Sized + Eq
+    {
+        self.entries.iter_mut().find(|e| e.0.borrow() == key).map(|e| &mut e.1)
+    }
+
+    /// Return `true` if the list contains `key`.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where K: Borrow<Q>, Q: ?Sized + Eq
+    {
+        self.get(key).is_some()
+    }
+
+    /// Insert a key-value pair.
+    ///
+    /// If the key was already present, its value is replaced and the old
+    /// value is returned. New keys are appended at the end.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V>
+        where K: Eq
+    {
+        if let Some(slot) = self.entries.iter_mut().find(|e| e.0 == key) {
+            return Some(::std::mem::replace(&mut slot.1, value));
+        }
+        self.entries.push((key, value));
+        None
+    }
+
+    /// Return an iterator of the entries, in insertion order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter { iter: self.entries.iter() }
+    }
+}
+
+impl<K: Eq, V> FromIterator<(K, V)> for AssocList<K, V> {
+    fn from_iter<I>(iterable: I) -> Self
+        where I: IntoIterator<Item=(K, V)>
+    {
+        let mut list = AssocList::new();
+        for (k, v) in iterable {
+            let _ = list.insert(k, v);
+        }
+        list
+    }
+}
+
+impl<K, V> IntoIterator for AssocList<K, V> {
+    type Item = (K, V);
+    type IntoIter = smallvec::IntoIter<[(K, V); 8]>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a AssocList<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the entries of an [`AssocList`](struct.AssocList.html).
+#[derive(Clone, Debug)]
+pub struct Iter<'a, K: 'a, V: 'a> {
+    iter: slice::Iter<'a, (K, V)>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|e| (&e.0, &e.1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
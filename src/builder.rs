@@ -0,0 +1,188 @@
+//! Builders for maps and sets whose entries are assembled at runtime.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::hash::Hash;
+
+/// A builder for maps, for entries that are collected across control flow
+/// where a macro literal doesn't fit.
+///
+/// As with [`hashmap!`](../macro.hashmap.html), the map has capacity for at
+/// least all its entries, and a later entry for a key replaces an earlier one.
+///
+/// ```
+/// use maplit::MapBuilder;
+///
+/// let verbose = true;
+/// let mut builder = MapBuilder::new().capacity(4).entry("a", 1);
+/// if verbose {
+///     builder = builder.entry("b", 2);
+/// }
+/// let map = builder.entries(vec![("c", 3), ("a", 0)]).build_hashmap();
+/// assert_eq!(map.len(), 3);
+/// assert_eq!(map["a"], 0);
+/// assert!(map.capacity() >= 4);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MapBuilder<K, V> {
+    entries: Vec<(K, V)>,
+    capacity: usize,
+}
+
+impl<K, V> MapBuilder<K, V> {
+    /// Create a new builder with no entries.
+    pub fn new() -> Self {
+        MapBuilder { entries: Vec::new(), capacity: 0 }
+    }
+
+    /// Add an entry.
+    pub fn entry(mut self, key: K, value: V) -> Self {
+        self.entries.push((key, value));
+        self
+    }
+
+    /// Add all the entries of `iterable`.
+    pub fn entries<I>(mut self, iterable: I) -> Self
+        where I: IntoIterator<Item=(K, V)>
+    {
+        self.entries.extend(iterable);
+        self
+    }
+
+    /// Reserve room for at least `capacity` entries in the built map, for
+    /// maps that grow after they are built.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Return the number of entries added so far, counting repeated keys.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return `true` if no entries have been added.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Build a `HashMap`.
+    pub fn build_hashmap(self) -> HashMap<K, V>
+        where K: Hash + Eq
+    {
+        let mut map = HashMap::with_capacity(self.capacity.max(self.entries.len()));
+        map.extend(self.entries);
+        map
+    }
+
+    /// Build a `BTreeMap`. The capacity is ignored.
+    pub fn build_btreemap(self) -> BTreeMap<K, V>
+        where K: Ord
+    {
+        self.entries.into_iter().collect()
+    }
+}
+
+impl<K, V> Default for MapBuilder<K, V> {
+    fn default() -> Self {
+        MapBuilder::new()
+    }
+}
+
+impl<K, V> Extend<(K, V)> for MapBuilder<K, V> {
+    fn extend<I>(&mut self, iterable: I)
+        where I: IntoIterator<Item=(K, V)>
+    {
+        self.entries.extend(iterable);
+    }
+}
+
+/// A builder for sets, the companion of [`MapBuilder`](struct.MapBuilder.html).
+///
+/// There is no duplicate policy to choose: as with `hashset!` and
+/// [`add_all!`](../macro.add_all.html), a repeated element is added once,
+/// and unlike a map there is no value that a policy could keep or replace.
+///
+/// ```
+/// use maplit::SetBuilder;
+///
+/// let set = SetBuilder::new()
+///     .elem("a")
+///     .elems(vec!["b", "a"])
+///     .capacity(8)
+///     .build_hashset();
+/// assert_eq!(set.len(), 2);
+/// assert!(set.capacity() >= 8);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SetBuilder<T> {
+    elems: Vec<T>,
+    capacity: usize,
+}
+
+impl<T> SetBuilder<T> {
+    /// Create a new builder with no elements.
+    pub fn new() -> Self {
+        SetBuilder { elems: Vec::new(), capacity: 0 }
+    }
+
+    /// Add an element.
+    pub fn elem(mut self, elem: T) -> Self {
+        self.elems.push(elem);
+        self
+    }
+
+    /// Add all the elements of `iterable`.
+    pub fn elems<I>(mut self, iterable: I) -> Self
+        where I: IntoIterator<Item=T>
+    {
+        self.elems.extend(iterable);
+        self
+    }
+
+    /// Reserve room for at least `capacity` elements in the built set, for
+    /// sets that grow after they are built.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Return the number of elements added so far, counting duplicates.
+    pub fn len(&self) -> usize {
+        self.elems.len()
+    }
+
+    /// Return `true` if no elements have been added.
+    pub fn is_empty(&self) -> bool {
+        self.elems.is_empty()
+    }
+
+    /// Build a `HashSet`.
+    pub fn build_hashset(self) -> HashSet<T>
+        where T: Hash + Eq
+    {
+        let mut set = HashSet::with_capacity(self.capacity.max(self.elems.len()));
+        set.extend(self.elems);
+        set
+    }
+
+    /// Build a `BTreeSet`. The capacity is ignored.
+    pub fn build_btreeset(self) -> BTreeSet<T>
+        where T: Ord
+    {
+        self.elems.into_iter().collect()
+    }
+}
+
+impl<T> Default for SetBuilder<T> {
+    fn default() -> Self {
+        SetBuilder::new()
+    }
+}
+
+impl<T> Extend<T> for SetBuilder<T> {
+    fn extend<I>(&mut self, iterable: I)
+        where I: IntoIterator<Item=T>
+    {
+        self.elems.extend(iterable);
+    }
+}
@@ -0,0 +1,168 @@
+//! A string-keyed map that ignores ASCII case in its keys.
+
+use std::borrow::Borrow;
+use std::collections::{hash_map, HashMap};
+use std::hash::{Hash, Hasher};
+use std::ops::Index;
+
+/// A borrowed key that hashes and compares ignoring ASCII case.
+#[derive(Debug)]
+#[repr(transparent)]
+struct Key(str);
+
+impl Key {
+    fn new(s: &str) -> &Key {
+        // Key is a transparent wrapper around str
+        unsafe { &*(s as *const str as *const Key) }
+    }
+}
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Key) -> bool {
+        self.0.eq_ignore_ascii_case(&other.0)
+    }
+}
+
+impl Eq for Key { }
+
+impl Hash for Key {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for byte in self.0.bytes() {
+            state.write_u8(byte.to_ascii_lowercase());
+        }
+        state.write_u8(0xff);
+    }
+}
+
+/// An owned key, keeping the spelling it was first inserted with.
+#[derive(Clone, Debug)]
+struct KeyBuf(String);
+
+impl Borrow<Key> for KeyBuf {
+    fn borrow(&self) -> &Key {
+        Key::new(&self.0)
+    }
+}
+
+impl PartialEq for KeyBuf {
+    fn eq(&self, other: &KeyBuf) -> bool {
+        Key::new(&self.0) == Key::new(&other.0)
+    }
+}
+
+impl Eq for KeyBuf { }
+
+impl Hash for KeyBuf {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Key::new(&self.0).hash(state)
+    }
+}
+
+/// A map with string keys that are compared ignoring ASCII case.
+///
+/// `"Content-Type"` and `"content-type"` are the same key. The map
+/// remembers the spelling a key was first inserted with, and that is the
+/// spelling returned when iterating.
+///
+/// Only ASCII letters are case folded, which is what protocols like HTTP
+/// specify.
+///
+/// Created with the [`cimap!`](macro.cimap.html) macro.
+#[derive(Clone, Debug)]
+pub struct CaseInsensitiveMap<V> {
+    map: HashMap<KeyBuf, V>,
+}
+
+impl<V> CaseInsensitiveMap<V> {
+    /// Create a new, empty map.
+    pub fn new() -> Self {
+        CaseInsensitiveMap::with_capacity(0)
+    }
+
+    /// Create a new, empty map with room for `capacity` entries.
+    pub fn with_capacity(capacity: usize) -> Self {
+        CaseInsensitiveMap { map: HashMap::with_capacity(capacity) }
+    }
+
+    /// Return the number of entries.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Return `true` if the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Insert a key-value pair, returning the old value if the key (in any
+    /// case) was already present.
+    pub fn insert<K: Into<String>>(&mut self, key: K, value: V) -> Option<V> {
+        self.map.insert(KeyBuf(key.into()), value)
+    }
+
+    /// Return a reference to the value for `key`, if present.
+    pub fn get(&self, key: &str) -> Option<&V> {
+        self.map.get(Key::new(key))
+    }
+
+    /// Return a mutable reference to the value for `key`, if present.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut V> {
+        self.map.get_mut(Key::new(key))
+    }
+
+    /// Return `true` if the map contains `key`.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.map.contains_key(Key::new(key))
+    }
+
+    /// Remove `key` and return its value, if present.
+    pub fn remove(&mut self, key: &str) -> Option<V> {
+        self.map.remove(Key::new(key))
+    }
+
+    /// Return an iterator of the entries, in arbitrary order.
+    pub fn iter(&self) -> Iter<'_, V> {
+        Iter { iter: self.map.iter() }
+    }
+}
+
+impl<V> Default for CaseInsensitiveMap<V> {
+    fn default() -> Self {
+        CaseInsensitiveMap::new()
+    }
+}
+
+impl<V> Index<&str> for CaseInsensitiveMap<V> {
+    type Output = V;
+
+    /// **Panics** if the key is missing.
+    fn index(&self, key: &str) -> &V {
+        self.get(key).expect("CaseInsensitiveMap: key not found")
+    }
+}
+
+impl<'a, V> IntoIterator for &'a CaseInsensitiveMap<V> {
+    type Item = (&'a str, &'a V);
+    type IntoIter = Iter<'a, V>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the entries of a
+/// [`CaseInsensitiveMap`](struct.CaseInsensitiveMap.html).
+#[derive(Clone, Debug)]
+pub struct Iter<'a, V: 'a> {
+    iter: hash_map::Iter<'a, KeyBuf, V>,
+}
+
+impl<'a, V> Iterator for Iter<'a, V> {
+    type Item = (&'a str, &'a V);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(k, v)| (&k.0[..], v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
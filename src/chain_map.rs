@@ -0,0 +1,81 @@
+//! Layered lookup through a list of hash maps.
+
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+
+/// A view of several borrowed `HashMap`s as one map, where a lookup goes
+/// through the maps in order and the first hit wins.
+///
+/// The maps are not copied or merged, so it is always possible to find out
+/// which layer a value came from with [`find`](#method.find).
+///
+/// Created with the [`chainmap!`](macro.chainmap.html) macro.
+#[derive(Clone, Debug)]
+pub struct ChainMap<'a, K: 'a, V: 'a, S: 'a = RandomState>
+    where K: Hash + Eq, S: BuildHasher
+{
+    layers: Vec<&'a HashMap<K, V, S>>,
+}
+
+impl<'a, K, V, S> ChainMap<'a, K, V, S>
+    where K: Hash + Eq, S: BuildHasher
+{
+    /// Create a new chain map without any layers.
+    pub fn new() -> Self {
+        ChainMap { layers: Vec::new() }
+    }
+
+    /// Add `map` as the last layer, with lower priority than all the
+    /// existing layers.
+    pub fn push_layer(&mut self, map: &'a HashMap<K, V, S>) {
+        self.layers.push(map);
+    }
+
+    /// Return the layers, highest priority first.
+    pub fn layers(&self) -> &[&'a HashMap<K, V, S>] {
+        &self.layers
+    }
+
+    /// Return the value for `key` from the first layer that has it.
+    pub fn get<Q>(&self, key: &Q) -> Option<&'a V>
+        where K: Borrow<Q>, Q: ?Sized + Hash + Eq
+    {
+        self.find(key).map(|(_, value)| value)
+    }
+
+    /// Return the index of the first layer that has `key`, together with
+    /// its value.
+    pub fn find<Q>(&self, key: &Q) -> Option<(usize, &'a V)>
+        where K: Borrow<Q>, Q: ?Sized + Hash + Eq
+    {
+        self.layers.iter().enumerate()
+            .filter_map(|(i, layer)| layer.get(key).map(|value| (i, value)))
+            .next()
+    }
+
+    /// Return `true` if any layer has `key`.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where K: Borrow<Q>, Q: ?Sized + Hash + Eq
+    {
+        self.layers.iter().any(|layer| layer.contains_key(key))
+    }
+
+    /// Return a map of every key in any layer to its resolved value.
+    pub fn flatten(&self) -> HashMap<&'a K, &'a V> {
+        let mut map = HashMap::new();
+        for layer in self.layers.iter().rev() {
+            map.extend(layer.iter());
+        }
+        map
+    }
+}
+
+impl<'a, K, V, S> Default for ChainMap<'a, K, V, S>
+    where K: Hash + Eq, S: BuildHasher
+{
+    fn default() -> Self {
+        ChainMap::new()
+    }
+}
@@ -0,0 +1,125 @@
+//! Procedural versions of `hashmap!` and `btreemap!` with precise errors.
+//!
+//! A mistake in a long `macro_rules!` literal, like a missing comma, is
+//! reported as "no rules expected the token" for the whole invocation. The
+//! macros here accept the same `key => value` entries, including the
+//! `@default` shorthand, but point at the token where an entry goes wrong and
+//! say which entry it is. Value and key modifiers like `@rc;` are not
+//! supported.
+//!
+//! Besides missing commas and `=>`, they catch a second `=>` in an entry,
+//! and an entry whose key and value are literals of the kinds that the other
+//! values and keys have, which is reported as "keys and values swapped?".
+//!
+//! An entry that ends at the end of a line doesn't need a comma, like a
+//! field in TOML, so that a long table doesn't fail to compile over one
+//! missing comma. The next entry must then start with something that can't
+//! continue the value: a key like `- 1` or `(a, b)` would be parsed as part
+//! of the value on the line before. The line numbers are only known with
+//! Rust 1.88 or later, so older compilers still require the commas.
+//!
+//! ```
+//! # extern crate maplit;
+//! use maplit::checked::btreemap;
+//!
+//! # fn main() {
+//! let map = btreemap!{
+//!     "a" => 1
+//!     "b" => 2
+//! };
+//! assert_eq!(map["b"], 2);
+//! # }
+//! ```
+//!
+//! As in the `macro_rules!` versions, the entries are inserted in blocks of
+//! up to 256, so that a giant generated table isn't one huge block.
+//!
+//! Import them in place of the `macro_rules!` versions:
+//!
+//! ```
+//! # extern crate maplit;
+//! use maplit::checked::hashmap;
+//!
+//! # fn main() {
+//! let map = hashmap!{
+//!     "a" => 1,
+//!     "b" => @default,
+//! };
+//! assert_eq!(map["b"], 0);
+//! # }
+//! ```
+//!
+//! Requires crate feature `"proc-macro"`.
+
+/// Create a **HashMap** from a list of key-value pairs.
+///
+/// Like [`hashmap!`](../macro.hashmap.html), with errors that point at the
+/// malformed entry.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate maplit;
+/// use maplit::checked::hashmap;
+///
+/// # fn main() {
+/// let map = hashmap!{
+///     "a" => 1,
+///     "b" => 2,
+/// };
+/// assert_eq!(map["a"], 1);
+/// # }
+/// ```
+///
+/// A missing comma between entries on the same line is reported as
+/// "expected `,` after entry 2, found `"c"`", at the key of the third entry:
+///
+/// ```compile_fail
+/// # extern crate maplit;
+/// use maplit::checked::hashmap;
+///
+/// # fn main() {
+/// let map = hashmap!{
+///     "a" => 1,
+///     "b" => 2 "c" => 3,
+/// };
+/// # }
+/// ```
+///
+/// A key and value that are the wrong way around are reported as "keys and
+/// values swapped in entry 3?":
+///
+/// ```compile_fail
+/// # extern crate maplit;
+/// use maplit::checked::hashmap;
+///
+/// # fn main() {
+/// let map = hashmap!{
+///     "a" => 1,
+///     "b" => 2,
+///     3 => "c",
+/// };
+/// # }
+/// ```
+pub use maplit_macros::hashmap;
+
+/// Create a **BTreeMap** from a list of key-value pairs.
+///
+/// Like [`btreemap!`](../macro.btreemap.html), with errors that point at the
+/// malformed entry.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate maplit;
+/// use maplit::checked::btreemap;
+///
+/// # fn main() {
+/// let map = btreemap!{
+///     "a" => 1,
+///     "b" => 2,
+/// };
+/// assert_eq!(map["b"], 2);
+/// # }
+/// ```
+pub use maplit_macros::btreemap;
@@ -0,0 +1,22 @@
+//! Runtime support for [`config!`](macro.config.html).
+
+use std::env;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Return the value of the environment variable `name` parsed as `T`, or
+/// `default()` if it is not set.
+///
+/// Panics if the variable is set but can't be parsed.
+#[doc(hidden)]
+pub fn __config_var<T, F>(name: &str, default: F) -> T
+    where T: FromStr, T::Err: Display, F: FnOnce() -> T
+{
+    match env::var(name) {
+        Ok(text) => match text.parse() {
+            Ok(value) => value,
+            Err(err) => ::std::panic!("config!: invalid value {:?} for `{}`: {}", text, name, err),
+        },
+        Err(_) => default(),
+    }
+}
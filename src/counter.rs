@@ -0,0 +1,149 @@
+//! A multiset that counts occurrences of its elements.
+
+use std::borrow::Borrow;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::iter::FromIterator;
+use std::ops::{Add, AddAssign, Deref};
+
+/// A multiset of elements and their counts, backed by `HashMap<T, usize>`.
+///
+/// It dereferences to the inner map for read access.
+///
+/// Created with the [`counter!`](macro.counter.html) macro, or by collecting
+/// from an iterator.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Counter<T: Hash + Eq> {
+    counts: HashMap<T, usize>,
+}
+
+impl<T: Hash + Eq> Counter<T> {
+    /// Create a new, empty counter.
+    pub fn new() -> Self {
+        Counter { counts: HashMap::new() }
+    }
+
+    /// Create a new, empty counter with room for `capacity` distinct
+    /// elements.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Counter { counts: HashMap::with_capacity(capacity) }
+    }
+
+    /// Count one more occurrence of `item`.
+    pub fn add(&mut self, item: T) {
+        self.add_n(item, 1);
+    }
+
+    /// Count `n` more occurrences of `item`.
+    pub fn add_n(&mut self, item: T, n: usize) {
+        *self.counts.entry(item).or_insert(0) += n;
+    }
+
+    /// Return the count of `item`, which is zero if it is missing.
+    pub fn count<Q>(&self, item: &Q) -> usize
+        where T: Borrow<Q>, Q: ?Sized + Hash + Eq
+    {
+        self.counts.get(item).cloned().unwrap_or(0)
+    }
+
+    /// Return the sum of all counts.
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Return the elements and their counts, most common first.
+    ///
+    /// Elements with equal counts are in arbitrary order.
+    pub fn most_common(&self) -> Vec<(&T, usize)> {
+        let mut entries: Vec<_> = self.counts.iter().map(|(k, &n)| (k, n)).collect();
+        entries.sort_by_key(|e| Reverse(e.1));
+        entries
+    }
+
+    /// Add all the counts of `other` to this counter.
+    pub fn merge(&mut self, other: Counter<T>) {
+        for (item, n) in other.counts {
+            self.add_n(item, n);
+        }
+    }
+
+    /// Subtract the counts of `other` from this counter.
+    ///
+    /// Counts saturate at zero, and elements whose count reaches zero are
+    /// removed.
+    pub fn subtract(&mut self, other: &Counter<T>) {
+        for (item, &n) in &other.counts {
+            let remove = match self.counts.get_mut(item) {
+                Some(count) => {
+                    *count = count.saturating_sub(n);
+                    *count == 0
+                }
+                None => false,
+            };
+            if remove {
+                let _ = self.counts.remove(item);
+            }
+        }
+    }
+
+    /// Unwrap the inner `HashMap`.
+    pub fn into_inner(self) -> HashMap<T, usize> {
+        self.counts
+    }
+}
+
+impl<T: Hash + Eq> Default for Counter<T> {
+    fn default() -> Self {
+        Counter::new()
+    }
+}
+
+impl<T: Hash + Eq> Deref for Counter<T> {
+    type Target = HashMap<T, usize>;
+    fn deref(&self) -> &HashMap<T, usize> {
+        &self.counts
+    }
+}
+
+impl<T: Hash + Eq> FromIterator<T> for Counter<T> {
+    fn from_iter<I>(iterable: I) -> Self
+        where I: IntoIterator<Item=T>
+    {
+        let mut counter = Counter::new();
+        counter.extend(iterable);
+        counter
+    }
+}
+
+impl<T: Hash + Eq> Extend<T> for Counter<T> {
+    fn extend<I>(&mut self, iterable: I)
+        where I: IntoIterator<Item=T>
+    {
+        for item in iterable {
+            self.add(item);
+        }
+    }
+}
+
+impl<T: Hash + Eq> Add for Counter<T> {
+    type Output = Counter<T>;
+    fn add(mut self, other: Counter<T>) -> Counter<T> {
+        self.merge(other);
+        self
+    }
+}
+
+impl<T: Hash + Eq> AddAssign for Counter<T> {
+    fn add_assign(&mut self, other: Counter<T>) {
+        self.merge(other);
+    }
+}
+
+impl<T: Hash + Eq> IntoIterator for Counter<T> {
+    type Item = (T, usize);
+    type IntoIter = ::std::collections::hash_map::IntoIter<T, usize>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter()
+    }
+}
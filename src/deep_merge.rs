@@ -0,0 +1,101 @@
+//! Recursive merging of nested maps.
+
+use std::collections::{btree_map, hash_map, BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+
+/// Values that can be merged recursively, used by
+/// [`deep_merge!`](../macro.deep_merge.html).
+///
+/// For maps, the entries of the other map are merged into this one, and the
+/// values of keys present in both are merged in turn. Other values are
+/// leaves: merging replaces them with the other value.
+///
+/// The trait is implemented for the std maps, and as a leaf for the
+/// primitive types, `String`, `&str`, `Vec`, `Option` and `Box`. For your
+/// own leaf types, implement `deep_merge_to_depth` as `*self = other`.
+pub trait DeepMerge {
+    /// Merge `other` into `self`, with the values of `other` taking
+    /// precedence.
+    fn deep_merge(&mut self, other: Self)
+        where Self: Sized
+    {
+        self.deep_merge_to_depth(other, ::std::usize::MAX);
+    }
+
+    /// Merge `other` into `self`, merging at most `depth` levels of nested
+    /// maps. Below that, the maps of `other` replace those in `self`.
+    fn deep_merge_to_depth(&mut self, other: Self, depth: usize);
+}
+
+impl<K, V, S> DeepMerge for HashMap<K, V, S>
+    where K: Hash + Eq, V: DeepMerge, S: BuildHasher
+{
+    fn deep_merge_to_depth(&mut self, other: Self, depth: usize) {
+        if depth == 0 {
+            *self = other;
+            return;
+        }
+        for (key, value) in other {
+            match self.entry(key) {
+                hash_map::Entry::Occupied(mut e) => e.get_mut().deep_merge_to_depth(value, depth - 1),
+                hash_map::Entry::Vacant(e) => {
+                    let _ = e.insert(value);
+                }
+            }
+        }
+    }
+}
+
+impl<K, V> DeepMerge for BTreeMap<K, V>
+    where K: Ord, V: DeepMerge
+{
+    fn deep_merge_to_depth(&mut self, other: Self, depth: usize) {
+        if depth == 0 {
+            *self = other;
+            return;
+        }
+        for (key, value) in other {
+            match self.entry(key) {
+                btree_map::Entry::Occupied(mut e) => e.get_mut().deep_merge_to_depth(value, depth - 1),
+                btree_map::Entry::Vacant(e) => {
+                    let _ = e.insert(value);
+                }
+            }
+        }
+    }
+}
+
+macro_rules! leaf_impls {
+    ($($t:ty),*) => {
+        $(
+            impl DeepMerge for $t {
+                fn deep_merge_to_depth(&mut self, other: Self, _depth: usize) {
+                    *self = other;
+                }
+            }
+        )*
+    };
+}
+
+leaf_impls!((), bool, char, String, &str,
+            i8, i16, i32, i64, i128, isize,
+            u8, u16, u32, u64, u128, usize,
+            f32, f64);
+
+impl<T> DeepMerge for Vec<T> {
+    fn deep_merge_to_depth(&mut self, other: Self, _depth: usize) {
+        *self = other;
+    }
+}
+
+impl<T> DeepMerge for Option<T> {
+    fn deep_merge_to_depth(&mut self, other: Self, _depth: usize) {
+        *self = other;
+    }
+}
+
+impl<T: ?Sized> DeepMerge for Box<T> {
+    fn deep_merge_to_depth(&mut self, other: Self, _depth: usize) {
+        *self = other;
+    }
+}
@@ -0,0 +1,113 @@
+//! A hash map that produces default values for missing keys.
+
+use std::borrow::{Borrow, Cow};
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::ops::{Deref, DerefMut, Index};
+
+/// A `HashMap` with a factory for default values, in the style of Python's
+/// `defaultdict`.
+///
+/// It dereferences to the inner `HashMap`, so all the usual map methods are
+/// available, and `get` returns `None` for a missing key. In addition,
+/// missing keys can be read or filled in with a value from the factory.
+///
+/// Indexing with a missing key returns a reference to a default value that
+/// the factory made once, when the map was created, instead of panicking.
+///
+/// Created with the [`defaultmap!`](macro.defaultmap.html) macro,
+/// `DefaultHashMap::new()` (using `Default`) or
+/// `DefaultHashMap::with_factory`.
+#[derive(Clone)]
+pub struct DefaultHashMap<K, V, F = fn() -> V> {
+    map: HashMap<K, V>,
+    factory: F,
+    default: V,
+}
+
+impl<K: Hash + Eq, V: Default> DefaultHashMap<K, V> {
+    /// Create a new, empty map that uses `V::default()` for missing keys.
+    pub fn new() -> Self {
+        DefaultHashMap::with_factory(V::default)
+    }
+}
+
+impl<K: Hash + Eq, V: Default> Default for DefaultHashMap<K, V> {
+    fn default() -> Self {
+        DefaultHashMap::new()
+    }
+}
+
+impl<K, V, F> DefaultHashMap<K, V, F>
+    where K: Hash + Eq, F: Fn() -> V
+{
+    /// Create a new, empty map that calls `factory` for missing keys.
+    pub fn with_factory(factory: F) -> Self {
+        DefaultHashMap::with_capacity_and_factory(0, factory)
+    }
+
+    /// Create a new, empty map with room for `capacity` entries that calls
+    /// `factory` for missing keys.
+    pub fn with_capacity_and_factory(capacity: usize, factory: F) -> Self {
+        let default = factory();
+        DefaultHashMap { map: HashMap::with_capacity(capacity), factory, default }
+    }
+
+    /// Return the value for `key`, or a new default value if it is missing.
+    ///
+    /// The map is not modified.
+    pub fn get_or_default<Q>(&self, key: &Q) -> Cow<'_, V>
+        where K: Borrow<Q>, Q: ?Sized + Hash + Eq, V: Clone
+    {
+        match self.map.get(key) {
+            Some(value) => Cow::Borrowed(value),
+            None => Cow::Owned((self.factory)()),
+        }
+    }
+
+    /// Return a mutable reference to the value for `key`, inserting a new
+    /// default value first if it is missing.
+    pub fn get_or_insert(&mut self, key: K) -> &mut V {
+        let factory = &self.factory;
+        self.map.entry(key).or_insert_with(factory)
+    }
+
+    /// Unwrap the inner `HashMap`.
+    pub fn into_inner(self) -> HashMap<K, V> {
+        self.map
+    }
+}
+
+impl<K, V, F, Q> Index<&Q> for DefaultHashMap<K, V, F>
+    where K: Hash + Eq + Borrow<Q>, Q: ?Sized + Hash + Eq
+{
+    type Output = V;
+
+    /// Return the value for `key`, or the map's default value if it is
+    /// missing.
+    fn index(&self, key: &Q) -> &V {
+        self.map.get(key).unwrap_or(&self.default)
+    }
+}
+
+impl<K, V, F> Deref for DefaultHashMap<K, V, F> {
+    type Target = HashMap<K, V>;
+    fn deref(&self) -> &HashMap<K, V> {
+        &self.map
+    }
+}
+
+impl<K, V, F> DerefMut for DefaultHashMap<K, V, F> {
+    fn deref_mut(&mut self) -> &mut HashMap<K, V> {
+        &mut self.map
+    }
+}
+
+impl<K, V, F> fmt::Debug for DefaultHashMap<K, V, F>
+    where K: Hash + Eq + fmt::Debug, V: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.map, f)
+    }
+}
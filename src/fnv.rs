@@ -0,0 +1,62 @@
+//! The FNV-1a hasher, used by the maps that hash keys at compile time.
+
+use std::hash::{BuildHasherDefault, Hasher};
+
+/// A `BuildHasher` for [`FnvHasher`](struct.FnvHasher.html).
+pub type FnvBuildHasher = BuildHasherDefault<FnvHasher>;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// The 64-bit FNV-1a hasher.
+///
+/// Integers are hashed as little endian bytes, and `usize` and `isize` as
+/// 64-bit integers, so that a hash is the same on every target. That is what
+/// lets the procedural macros compute hashes on the host for any target.
+#[derive(Clone, Copy, Debug)]
+pub struct FnvHasher {
+    hash: u64,
+}
+
+impl Default for FnvHasher {
+    fn default() -> Self {
+        FnvHasher { hash: FNV_OFFSET }
+    }
+}
+
+impl Hasher for FnvHasher {
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.hash ^= u64::from(b);
+            self.hash = self.hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.write(&[i]);
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+}
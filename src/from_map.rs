@@ -0,0 +1,114 @@
+//! Construction of a struct from a map of its fields.
+
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::str::FromStr;
+
+/// A struct that can be constructed from a map from field names to strings.
+///
+/// Derive it with `#[derive(FromMap)]`. Each field is looked up by its name
+/// and parsed with `FromStr`, so the field types must implement `FromStr`.
+/// A field of type `Option<T>` is `None` when its key is missing.
+///
+/// The `#[map(...)]` attribute changes how a field is looked up:
+///
+/// - `#[map(rename = "name")]` uses `name` as the key.
+/// - `#[map(default)]` uses `Default::default()` when the key is missing,
+///   and `#[map(default = expr)]` uses `expr`.
+/// - `#[map(skip)]` doesn't look up the field, and uses `Default::default()`.
+///
+/// Requires crate feature `"proc-macro"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// use maplit::{FromMap, FromMapError};
+///
+/// #[derive(FromMap, Debug, PartialEq)]
+/// struct Server {
+///     host: String,
+///     #[map(default = 8080)]
+///     port: u16,
+///     #[map(rename = "log")]
+///     log_level: Option<u8>,
+/// }
+///
+/// # fn main() {
+/// let server = Server::from_map(&hashmap!{"host" => "localhost", "log" => "2"});
+/// assert_eq!(server, Ok(Server { host: "localhost".to_string(), port: 8080, log_level: Some(2) }));
+///
+/// let server = Server::from_map(&hashmap!{"port" => "80"});
+/// assert_eq!(server, Err(FromMapError::MissingField("host")));
+/// # }
+/// ```
+pub trait FromMap: Sized {
+    /// Construct a value from the fields in `map`.
+    fn from_map<M: ?Sized + FieldMap>(map: &M) -> Result<Self, FromMapError>;
+}
+
+/// A map that [`FromMap`](trait.FromMap.html) can read fields from.
+pub trait FieldMap {
+    /// Return the string for the field `name`, if present.
+    fn field(&self, name: &str) -> Option<&str>;
+}
+
+impl<K, V, S> FieldMap for HashMap<K, V, S>
+    where K: Borrow<str> + Hash + Eq, V: AsRef<str>, S: BuildHasher
+{
+    fn field(&self, name: &str) -> Option<&str> {
+        self.get(name).map(AsRef::as_ref)
+    }
+}
+
+impl<K, V> FieldMap for BTreeMap<K, V>
+    where K: Borrow<str> + Ord, V: AsRef<str>
+{
+    fn field(&self, name: &str) -> Option<&str> {
+        self.get(name).map(AsRef::as_ref)
+    }
+}
+
+/// The error of [`FromMap::from_map`](trait.FromMap.html#tymethod.from_map).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FromMapError {
+    /// The key of a field without a default is missing.
+    MissingField(&'static str),
+    /// The string of a field can't be parsed.
+    InvalidField {
+        /// The key of the field.
+        field: &'static str,
+        /// The string in the map.
+        value: String,
+        /// The parse error.
+        message: String,
+    },
+}
+
+impl fmt::Display for FromMapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FromMapError::MissingField(field) => write!(f, "missing field `{}`", field),
+            FromMapError::InvalidField { field, ref value, ref message } => {
+                write!(f, "invalid value {:?} for field `{}`: {}", value, field, message)
+            }
+        }
+    }
+}
+
+impl Error for FromMapError {}
+
+/// Parse the string `value` of the field `field`.
+#[doc(hidden)]
+pub fn __parse_field<T>(field: &'static str, value: &str) -> Result<T, FromMapError>
+    where T: FromStr, T::Err: fmt::Display
+{
+    value.parse().map_err(|err: T::Err| FromMapError::InvalidField {
+        field,
+        value: value.to_string(),
+        message: err.to_string(),
+    })
+}
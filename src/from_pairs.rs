@@ -0,0 +1,56 @@
+//! Functions that build maps and sets from slices, for code that can't use
+//! the macros, like code generators and FFI layers.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::hash::Hash;
+
+/// Create a `HashMap` from a slice of key-value pairs, cloning them.
+///
+/// As with [`hashmap!`](macro.hashmap.html), the map is allocated with
+/// capacity for all the pairs, and a later pair for a key replaces an
+/// earlier one.
+///
+/// ```
+/// let map = maplit::hashmap_from_pairs(&[("a", 1), ("b", 2), ("a", 3)]);
+/// assert_eq!(map.len(), 2);
+/// assert_eq!(map["a"], 3);
+/// ```
+pub fn hashmap_from_pairs<K, V>(pairs: &[(K, V)]) -> HashMap<K, V>
+    where K: Hash + Eq + Clone, V: Clone
+{
+    let mut map = HashMap::with_capacity(pairs.len());
+    map.extend(pairs.iter().cloned());
+    map
+}
+
+/// Create a `BTreeMap` from a slice of key-value pairs, cloning them.
+///
+/// A later pair for a key replaces an earlier one.
+pub fn btreemap_from_pairs<K, V>(pairs: &[(K, V)]) -> BTreeMap<K, V>
+    where K: Ord + Clone, V: Clone
+{
+    pairs.iter().cloned().collect()
+}
+
+/// Create a `HashSet` from a slice of elements, cloning them.
+///
+/// The set is allocated with capacity for all the elements.
+pub fn hashset_from_elems<T>(elems: &[T]) -> HashSet<T>
+    where T: Hash + Eq + Clone
+{
+    let mut set = HashSet::with_capacity(elems.len());
+    set.extend(elems.iter().cloned());
+    set
+}
+
+/// Create a `BTreeSet` from a slice of elements, cloning them.
+///
+/// ```
+/// let set = maplit::btreeset_from_elems(&[3, 1, 2, 1]);
+/// assert_eq!(set.into_iter().collect::<Vec<_>>(), [1, 2, 3]);
+/// ```
+pub fn btreeset_from_elems<T>(elems: &[T]) -> BTreeSet<T>
+    where T: Ord + Clone
+{
+    elems.iter().cloned().collect()
+}
@@ -0,0 +1,64 @@
+//! Helpers for sparse grids stored as `HashMap<(usize, usize), T>`.
+//!
+//! Grids like this are created with the [`grid!`](../macro.grid.html) macro.
+//! Coordinates are `(row, column)`.
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+/// Return the smallest and largest coordinates in use, as
+/// `((min_row, min_col), (max_row, max_col))`, or `None` if the grid is
+/// empty.
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+/// let g = grid!{(1, 4) => 'a', (3, 2) => 'b'};
+/// assert_eq!(maplit::grid::bounds(&g), Some(((1, 2), (3, 4))));
+/// # }
+/// ```
+pub fn bounds<T, S>(grid: &HashMap<(usize, usize), T, S>)
+    -> Option<((usize, usize), (usize, usize))>
+    where S: BuildHasher
+{
+    let mut keys = grid.keys();
+    let &(r, c) = keys.next()?;
+    let mut min = (r, c);
+    let mut max = (r, c);
+    for &(r, c) in keys {
+        min = (min.0.min(r), min.1.min(c));
+        max = (max.0.max(r), max.1.max(c));
+    }
+    Some((min, max))
+}
+
+/// Return the up to four orthogonal neighbors of `pos` that are present in
+/// the grid, in the order up, left, right, down.
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+/// let g = grid![
+///     ['#', '.'],
+///     ['.', '#'],
+/// ];
+/// let n = maplit::grid::neighbors(&g, (0, 0));
+/// assert_eq!(n, [((0, 1), &'.'), ((1, 0), &'.')]);
+/// # }
+/// ```
+pub fn neighbors<T, S>(grid: &HashMap<(usize, usize), T, S>, pos: (usize, usize))
+    -> Vec<((usize, usize), &T)>
+    where S: BuildHasher
+{
+    let (r, c) = pos;
+    let candidates = [
+        r.checked_sub(1).map(|r| (r, c)),
+        c.checked_sub(1).map(|c| (r, c)),
+        c.checked_add(1).map(|c| (r, c)),
+        r.checked_add(1).map(|r| (r, c)),
+    ];
+    candidates.iter()
+        .filter_map(|&p| p)
+        .filter_map(|p| grid.get(&p).map(|v| (p, v)))
+        .collect()
+}
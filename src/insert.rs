@@ -0,0 +1,143 @@
+//! Traits that let the generic literal macros build any collection.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::hash::{BuildHasher, Hash};
+
+use sorted_vec_map::SortedVecMap;
+
+/// A map that [`map_of!`](macro.map_of.html) can build.
+///
+/// Implement this, together with `Default`, to use the literal syntax with
+/// your own map types.
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// use maplit::MapInsert;
+///
+/// #[derive(Default)]
+/// struct Env(Vec<String>);
+///
+/// impl MapInsert<&'static str, &'static str> for Env {
+///     fn map_insert(&mut self, key: &'static str, value: &'static str) {
+///         self.0.push(format!("{}={}", key, value));
+///     }
+/// }
+///
+/// # fn main() {
+/// let env = map_of!(Env; "LANG" => "C", "TZ" => "UTC");
+/// assert_eq!(env.0, ["LANG=C", "TZ=UTC"]);
+/// # }
+/// ```
+pub trait MapInsert<K, V> {
+    /// Insert an entry. Whether it replaces an existing entry for the same
+    /// key is up to the map.
+    fn map_insert(&mut self, key: K, value: V);
+
+    /// Make room for `additional` more entries, if the map supports it.
+    ///
+    /// The default implementation does nothing.
+    fn reserve_entries(&mut self, additional: usize) {
+        let _ = additional;
+    }
+}
+
+impl<K, V, S> MapInsert<K, V> for HashMap<K, V, S>
+    where K: Hash + Eq, S: BuildHasher
+{
+    fn map_insert(&mut self, key: K, value: V) {
+        let _ = self.insert(key, value);
+    }
+
+    fn reserve_entries(&mut self, additional: usize) {
+        self.reserve(additional);
+    }
+}
+
+impl<K: Ord, V> MapInsert<K, V> for BTreeMap<K, V> {
+    fn map_insert(&mut self, key: K, value: V) {
+        let _ = self.insert(key, value);
+    }
+}
+
+impl<K: Ord, V> MapInsert<K, V> for SortedVecMap<K, V> {
+    fn map_insert(&mut self, key: K, value: V) {
+        let _ = self.insert(key, value);
+    }
+}
+
+/// A set that [`set_of!`](macro.set_of.html) can build.
+///
+/// Implement this, together with `Default`, to use the literal syntax with
+/// your own set types.
+pub trait SetAdd<T> {
+    /// Add an element. Whether duplicates are kept is up to the set.
+    fn set_add(&mut self, elem: T);
+
+    /// Make room for `additional` more elements, if the set supports it.
+    ///
+    /// The default implementation does nothing.
+    fn reserve_elems(&mut self, additional: usize) {
+        let _ = additional;
+    }
+}
+
+impl<T, S> SetAdd<T> for HashSet<T, S>
+    where T: Hash + Eq, S: BuildHasher
+{
+    fn set_add(&mut self, elem: T) {
+        let _ = self.insert(elem);
+    }
+
+    fn reserve_elems(&mut self, additional: usize) {
+        self.reserve(additional);
+    }
+}
+
+impl<T: Ord> SetAdd<T> for BTreeSet<T> {
+    fn set_add(&mut self, elem: T) {
+        let _ = self.insert(elem);
+    }
+}
+
+/// A sorted vector used as a set: elements are inserted in order and
+/// duplicates are skipped.
+impl<T: Ord> SetAdd<T> for Vec<T> {
+    fn set_add(&mut self, elem: T) {
+        if let Err(i) = self.binary_search(&elem) {
+            self.insert(i, elem);
+        }
+    }
+
+    fn reserve_elems(&mut self, additional: usize) {
+        self.reserve(additional);
+    }
+}
+
+/// Insert all `entries` into `map`, in order, taking each out of its slot.
+///
+/// The literal macros pass their entries to this in slices of up to 256,
+/// instead of expanding to one `insert` call per entry. It is generic only
+/// over the map and entry types, not the number of entries, so the insert
+/// loop is compiled once per map type rather than once per call site.
+#[doc(hidden)]
+pub fn __insert_all<M, K, V>(map: &mut M, entries: &mut [Option<(K, V)>])
+    where M: MapInsert<K, V>
+{
+    for entry in entries {
+        if let Some((key, value)) = entry.take() {
+            map.map_insert(key, value);
+        }
+    }
+}
+
+/// Add all `elems` to `set`, like [`__insert_all`](fn.__insert_all.html).
+#[doc(hidden)]
+pub fn __add_all<S, T>(set: &mut S, elems: &mut [Option<T>])
+    where S: SetAdd<T>
+{
+    for elem in elems {
+        if let Some(elem) = elem.take() {
+            set.set_add(elem);
+        }
+    }
+}
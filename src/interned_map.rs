@@ -0,0 +1,163 @@
+//! A map with interned string keys.
+
+use std::collections::{hash_map, HashMap};
+use std::sync::Arc;
+
+/// A cheap handle to a string stored in an [`Interner`](struct.Interner.html).
+///
+/// Symbols from the same interner are equal exactly when their strings are
+/// equal.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(u32);
+
+/// A string interner, which stores each distinct string once and hands out
+/// a [`Symbol`](struct.Symbol.html) for it.
+///
+/// The strings are shared through `Arc`, so an interner, and an
+/// `InternedMap` of `Send` and `Sync` values, can be shared between threads
+/// or stored in a static.
+#[derive(Clone, Debug, Default)]
+pub struct Interner {
+    symbols: HashMap<Arc<str>, Symbol>,
+    strings: Vec<Arc<str>>,
+}
+
+impl Interner {
+    /// Create a new, empty interner.
+    pub fn new() -> Self {
+        Interner::default()
+    }
+
+    /// Return the number of distinct strings.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Return `true` if no strings have been interned.
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Return the symbol for `s`, adding it to the interner if needed.
+    pub fn intern(&mut self, s: &str) -> Symbol {
+        if let Some(&symbol) = self.symbols.get(s) {
+            return symbol;
+        }
+        let symbol = Symbol(self.strings.len() as u32);
+        let s: Arc<str> = Arc::from(s);
+        self.strings.push(s.clone());
+        let _ = self.symbols.insert(s, symbol);
+        symbol
+    }
+
+    /// Return the symbol for `s`, if it has been interned.
+    pub fn get(&self, s: &str) -> Option<Symbol> {
+        self.symbols.get(s).cloned()
+    }
+
+    /// Return the string of `symbol`.
+    ///
+    /// **Panics** if `symbol` is not from this interner.
+    pub fn resolve(&self, symbol: Symbol) -> &str {
+        &self.strings[symbol.0 as usize]
+    }
+}
+
+/// A map from strings to values, where the keys are stored once in an
+/// [`Interner`](struct.Interner.html) and the map itself is keyed by
+/// [`Symbol`](struct.Symbol.html).
+///
+/// The interner is also available for string values through
+/// [`intern`](#method.intern), so that repeated strings in a large table are
+/// only stored once.
+///
+/// Created with the [`internedmap!`](../macro.internedmap.html) macro.
+#[derive(Clone, Debug)]
+pub struct InternedMap<V> {
+    interner: Interner,
+    map: HashMap<Symbol, V>,
+}
+
+impl<V> InternedMap<V> {
+    /// Create a new, empty map.
+    pub fn new() -> Self {
+        InternedMap::with_capacity(0)
+    }
+
+    /// Create a new, empty map with room for `capacity` entries.
+    pub fn with_capacity(capacity: usize) -> Self {
+        InternedMap { interner: Interner::new(), map: HashMap::with_capacity(capacity) }
+    }
+
+    /// Return the number of entries.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Return `true` if the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Return the interner of the map.
+    pub fn interner(&self) -> &Interner {
+        &self.interner
+    }
+
+    /// Intern `s` in the map's interner and return its symbol.
+    pub fn intern(&mut self, s: &str) -> Symbol {
+        self.interner.intern(s)
+    }
+
+    /// Insert a key-value pair, returning the old value if the key was
+    /// already present.
+    pub fn insert(&mut self, key: &str, value: V) -> Option<V> {
+        let symbol = self.interner.intern(key);
+        self.map.insert(symbol, value)
+    }
+
+    /// Return a reference to the value for `key`, if present.
+    pub fn get(&self, key: &str) -> Option<&V> {
+        self.interner.get(key).and_then(|symbol| self.map.get(&symbol))
+    }
+
+    /// Return a reference to the value for the key `symbol`, if present.
+    pub fn get_by_symbol(&self, symbol: Symbol) -> Option<&V> {
+        self.map.get(&symbol)
+    }
+
+    /// Return `true` if the map contains `key`.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Return an iterator of the entries, in arbitrary order.
+    pub fn iter(&self) -> Iter<'_, V> {
+        Iter { interner: &self.interner, iter: self.map.iter() }
+    }
+}
+
+impl<V> Default for InternedMap<V> {
+    fn default() -> Self {
+        InternedMap::new()
+    }
+}
+
+/// Iterator over the entries of an [`InternedMap`](struct.InternedMap.html).
+#[derive(Clone, Debug)]
+pub struct Iter<'a, V: 'a> {
+    interner: &'a Interner,
+    iter: hash_map::Iter<'a, Symbol, V>,
+}
+
+impl<'a, V> Iterator for Iter<'a, V> {
+    type Item = (&'a str, &'a V);
+    fn next(&mut self) -> Option<Self::Item> {
+        let interner = self.interner;
+        self.iter.next().map(|(&symbol, v)| (interner.resolve(symbol), v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
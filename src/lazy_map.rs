@@ -0,0 +1,100 @@
+//! A hash map with lazily computed values.
+//!
+//! Requires crate feature `"lazy-map"`, which needs Rust 1.80 or later.
+
+use std::borrow::Borrow;
+use std::cell::LazyCell;
+use std::collections::{hash_map, HashMap};
+use std::fmt;
+use std::hash::Hash;
+
+type Thunk<'a, V> = Box<dyn FnOnce() -> V + 'a>;
+
+/// A `HashMap` whose values are computed by a stored closure the first time
+/// they are accessed.
+///
+/// Values that are never read are never computed.
+///
+/// Created with the [`lazymap!`](macro.lazymap.html) macro.
+pub struct LazyMap<'a, K, V> {
+    map: HashMap<K, LazyCell<V, Thunk<'a, V>>>,
+}
+
+impl<'a, K: Hash + Eq, V> LazyMap<'a, K, V> {
+    /// Create a new, empty map.
+    pub fn new() -> Self {
+        LazyMap::with_capacity(0)
+    }
+
+    /// Create a new, empty map with room for `capacity` entries.
+    pub fn with_capacity(capacity: usize) -> Self {
+        LazyMap { map: HashMap::with_capacity(capacity) }
+    }
+
+    /// Return the number of entries.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Return `true` if the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Insert `key` with a closure that computes its value on first access.
+    ///
+    /// An existing entry for `key` is replaced.
+    pub fn insert<F>(&mut self, key: K, init: F)
+        where F: FnOnce() -> V + 'a
+    {
+        let init: Thunk<'a, V> = Box::new(init);
+        let _ = self.map.insert(key, LazyCell::new(init));
+    }
+
+    /// Return `true` if the map contains `key`. The value is not computed.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where K: Borrow<Q>, Q: ?Sized + Hash + Eq
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Return the value for `key`, computing it if this is the first access.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+        where K: Borrow<Q>, Q: ?Sized + Hash + Eq
+    {
+        self.map.get(key).map(|cell| LazyCell::force(cell))
+    }
+
+    /// Return an iterator of the keys. No values are computed.
+    pub fn keys(&self) -> Keys<'_, 'a, K, V> {
+        Keys { iter: self.map.keys() }
+    }
+}
+
+impl<'a, K: Hash + Eq, V> Default for LazyMap<'a, K, V> {
+    fn default() -> Self {
+        LazyMap::new()
+    }
+}
+
+impl<'a, K: fmt::Debug, V> fmt::Debug for LazyMap<'a, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.map.keys()).finish()
+    }
+}
+
+/// Iterator over the keys of a [`LazyMap`](struct.LazyMap.html).
+pub struct Keys<'b, 'a: 'b, K: 'b, V: 'b> {
+    iter: hash_map::Keys<'b, K, LazyCell<V, Thunk<'a, V>>>,
+}
+
+impl<'b, 'a, K, V> Iterator for Keys<'b, 'a, K, V> {
+    type Item = &'b K;
+    fn next(&mut self) -> Option<&'b K> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
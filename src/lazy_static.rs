@@ -0,0 +1,34 @@
+//! The lazily initialized static type of the `static_*!` macros.
+
+use std::ops::Deref;
+
+#[cfg(feature = "once_cell")]
+type Inner<T> = ::__once_cell::sync::Lazy<T>;
+#[cfg(not(feature = "once_cell"))]
+type Inner<T> = ::std::sync::LazyLock<T>;
+
+/// A value that is computed on first access, for use in a `static`.
+///
+/// Declared with [`static_hashmap!`](macro.static_hashmap.html) and the
+/// other `static_*!` macros, and dereferences to the value.
+///
+/// It is built on `std::sync::LazyLock`, which needs Rust 1.80, or with
+/// crate feature `"once_cell"` on `once_cell::sync::Lazy`, for older
+/// compilers. The type is the same either way.
+pub struct Lazy<T> {
+    inner: Inner<T>,
+}
+
+impl<T> Lazy<T> {
+    #[doc(hidden)]
+    pub const fn __new(init: fn() -> T) -> Self {
+        Lazy { inner: Inner::new(init) }
+    }
+}
+
+impl<T> Deref for Lazy<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
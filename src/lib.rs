@@ -25,8 +25,3945 @@
 //! Generic container macros already exist elsewhere, so those are not provided
 //! here at the moment.
 
+#[cfg(feature = "smallvec")]
+extern crate smallvec;
+#[cfg(feature = "arrayvec")]
+extern crate arrayvec;
+
+pub mod literal_map;
+pub use literal_map::LiteralMap;
+mod default_map;
+pub use default_map::DefaultHashMap;
+mod counter;
+pub use counter::Counter;
+mod chain_map;
+pub use chain_map::ChainMap;
+#[cfg(feature = "lazy-map")]
+#[clippy::msrv = "1.80"]
+pub mod lazy_map;
+#[cfg(feature = "lazy-map")]
+pub use lazy_map::LazyMap;
+pub mod case_insensitive_map;
+pub use case_insensitive_map::CaseInsensitiveMap;
+pub mod interned_map;
+pub use interned_map::InternedMap;
+pub mod range_set;
+pub use range_set::RangeSet;
+pub mod trie_map;
+pub use trie_map::TrieMap;
+mod type_map;
+pub use type_map::TypeMap;
+pub mod sorted_vec_map;
+pub use sorted_vec_map::SortedVecMap;
+pub mod path_map;
+pub use path_map::PathMap;
+pub mod grid;
+mod builder;
+pub use builder::{MapBuilder, SetBuilder};
+mod insert;
+pub use insert::{MapInsert, SetAdd};
+#[doc(hidden)]
+pub use insert::{__add_all, __insert_all};
+pub mod deep_merge;
+pub use deep_merge::DeepMerge;
+mod from_pairs;
+pub mod map_diff;
+pub use map_diff::MapDiff;
+mod sorted_debug;
+pub use sorted_debug::SortedDebug;
+mod fnv;
+pub use fnv::{FnvBuildHasher, FnvHasher};
+pub mod perfect_map;
+pub use perfect_map::PerfectMap;
+pub mod value;
+pub use value::Value;
+mod config;
+#[doc(hidden)]
+pub use config::__config_var;
+mod assert;
+#[doc(hidden)]
+pub use assert::{__map_diff_message, __set_diff_message};
+pub use from_pairs::{btreemap_from_pairs, btreeset_from_elems, hashmap_from_pairs, hashset_from_elems};
+
+#[cfg(feature = "smallvec")]
+pub mod assoc_list;
+#[cfg(feature = "smallvec")]
+pub use assoc_list::AssocList;
+#[cfg(feature = "arrayvec")]
+mod array_set;
+#[cfg(feature = "arrayvec")]
+pub use array_set::ArraySet;
+#[cfg(any(feature = "static-maps", feature = "once_cell"))]
+mod lazy_static;
+#[cfg(any(feature = "static-maps", feature = "once_cell"))]
+pub use lazy_static::Lazy;
+#[cfg(feature = "proc-macro")]
+extern crate maplit_macros;
+#[cfg(feature = "proc-macro")]
+pub mod checked;
+#[cfg(feature = "proc-macro")]
+mod to_map;
+#[cfg(feature = "proc-macro")]
+pub use to_map::ToMap;
+#[cfg(feature = "proc-macro")]
+pub use maplit_macros::ToMap;
+#[cfg(feature = "proc-macro")]
+mod from_map;
+#[cfg(feature = "proc-macro")]
+pub use from_map::{FieldMap, FromMap, FromMapError};
+#[cfg(feature = "proc-macro")]
+#[doc(hidden)]
+pub use from_map::__parse_field;
+#[cfg(feature = "proc-macro")]
+pub use maplit_macros::FromMap;
+#[cfg(feature = "prehashed")]
+#[doc(hidden)]
+pub extern crate hashbrown as __hashbrown;
+#[cfg(feature = "prehashed")]
+#[clippy::msrv = "1.71"]
+pub mod prehashed;
+#[cfg(feature = "prehashed")]
+pub use prehashed::PrehashedMap;
+#[cfg(feature = "prehashed")]
+#[doc(hidden)]
+pub use prehashed::__insert_prehashed;
+#[cfg(feature = "prehashed")]
+#[doc(hidden)]
+pub use maplit_macros::prehashed_map as __prehashed_map;
+#[cfg(feature = "proc-macro")]
+#[doc(hidden)]
+pub use maplit_macros::perfectmap as __perfectmap;
+#[cfg(feature = "proc-macro")]
+#[doc(hidden)]
+pub use maplit_macros::config as __config;
+#[cfg(feature = "proc-macro")]
+#[doc(hidden)]
+pub use maplit_macros::dict as __dict;
+
+#[cfg(feature = "proc-macro")]
+/// Create a **HashMap** with a value for every variant of a fieldless enum.
+///
+/// The syntax is `exhaustive_map!(Enum; Variant => value, ...)`. It is a
+/// compile error if a variant of `Enum` is missing or listed twice, so the
+/// table can't silently fall behind when variants are added.
+///
+/// Requires crate feature `"proc-macro"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+///
+/// #[derive(PartialEq, Eq, Hash, Debug)]
+/// enum Color { Red, Green, Blue }
+///
+/// # fn main() {
+/// let hex = exhaustive_map!(Color;
+///     Red => "#f00",
+///     Green => "#0f0",
+///     Blue => "#00f",
+/// );
+/// assert_eq!(hex[&Color::Green], "#0f0");
+/// # }
+/// ```
+///
+/// Leaving out a variant doesn't compile:
+///
+/// ```compile_fail
+/// #[macro_use] extern crate maplit;
+///
+/// #[derive(PartialEq, Eq, Hash, Debug)]
+/// enum Color { Red, Green, Blue }
+///
+/// # fn main() {
+/// let hex = exhaustive_map!(Color; Red => "#f00", Green => "#0f0");
+/// # }
+/// ```
+pub use maplit_macros::exhaustive_map;
+
+#[cfg(feature = "proc-macro")]
+/// Create a **HashMap** from a file of `key=value` lines or a CSV file, read
+/// at compile time.
+///
+/// The syntax is `include_map!("path")`, where the path is relative to the
+/// root of the crate, the directory of its `Cargo.toml`. The map is a
+/// `HashMap<&'static str, &'static str>` built by the same code as a
+/// `hashmap!` of string literals, and the crate is rebuilt when the file
+/// changes.
+///
+/// Blank lines and lines starting with `#` or `!` are skipped, and the
+/// whitespace around keys and values is trimmed. There are no escapes or
+/// line continuations. A line without `=` or a repeated key is a compile
+/// error.
+///
+/// Requires crate feature `"proc-macro"`.
+///
+/// ## Example
+///
+/// With `tests/data/mime.properties` containing:
+///
+/// ```text
+/// # File extensions and their media types.
+/// html = text/html
+/// css = text/css
+/// png=image/png
+/// ```
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let mime = include_map!("tests/data/mime.properties");
+/// assert_eq!(mime["png"], "image/png");
+/// assert_eq!(mime.get("gif"), None);
+/// # }
+/// ```
+///
+/// ## CSV and TSV files
+///
+/// `include_map!(csv "path", key = "column", value = "column")` reads a
+/// CSV file with a header line, and maps the fields of the key column to
+/// the fields of the value column. `tsv` reads tab separated fields instead.
+/// With several value columns in parentheses, the values are tuples.
+///
+/// Fields are string literals, unless the column is followed by `as Type`,
+/// like `"population" as u64`. Then every field of the column must be a
+/// number or bool literal, which is given that type.
+///
+/// CSV fields may be quoted with `"`, with `""` for a quote inside them.
+/// Other fields are trimmed, and blank lines are skipped. A row with the
+/// wrong number of fields or a repeated key is a compile error.
+///
+/// With `tests/data/countries.csv` containing:
+///
+/// ```text
+/// code,name,population,eu
+/// se,Sweden,10551707,true
+/// no,Norway,5550203,false
+/// us,"United States, The",334914895,false
+/// ```
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let names = include_map!(csv "tests/data/countries.csv", key = "code", value = "name");
+/// assert_eq!(names["us"], "United States, The");
+///
+/// let info = include_map!(csv "tests/data/countries.csv",
+///                         key = "code",
+///                         value = ("population" as u64, "eu" as bool));
+/// assert_eq!(info["se"], (10551707, true));
+/// # }
+/// ```
+pub use maplit_macros::include_map;
+
+#[cfg(feature = "proc-macro")]
+/// Create a **HashSet** from a file with one element per line, read at
+/// compile time.
+///
+/// The syntax is `include_set!("path")`, where the path is relative to the
+/// root of the crate, the directory of its `Cargo.toml`. The set is a
+/// `HashSet<&'static str>`, and the crate is rebuilt when the file changes.
+///
+/// Lines are trimmed, and blank lines and lines starting with `#` are
+/// skipped. A repeated line is only added once.
+///
+/// See also [`btree_include_set!`](macro.btree_include_set.html).
+///
+/// Requires crate feature `"proc-macro"`.
+///
+/// ## Example
+///
+/// With `tests/data/stopwords.txt` containing:
+///
+/// ```text
+/// # English stop words
+/// a
+/// an
+/// the
+/// ```
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let stopwords = include_set!("tests/data/stopwords.txt");
+/// assert!(stopwords.contains("the"));
+/// assert!(!stopwords.contains("# English stop words"));
+/// # }
+/// ```
+pub use maplit_macros::include_set;
+
+#[cfg(feature = "proc-macro")]
+/// Create a **BTreeSet** from a file with one element per line, read at
+/// compile time.
+///
+/// Like [`include_set!`](macro.include_set.html), but the set is a
+/// `BTreeSet<&'static str>`.
+///
+/// Requires crate feature `"proc-macro"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let stopwords = btree_include_set!("tests/data/stopwords.txt");
+/// assert_eq!(stopwords.iter().next(), Some(&"a"));
+/// # }
+/// ```
+pub use maplit_macros::btree_include_set;
+
+#[cfg(feature = "proc-macro")]
+/// Create a **HashMap** of environment variables, read at compile time.
+///
+/// `env_map!(prefix = "APP_")` maps the name of every variable starting
+/// with `APP_` to its value. `env_map!{"NAME", "OTHER" => "default", ...}`
+/// maps the listed variables to their values, where a variable without a
+/// default must be set. The map is a `HashMap<&'static str, &'static str>`.
+///
+/// The values are read with `env!` and `option_env!`, so the crate is
+/// rebuilt when they change. With a prefix, the set of variables is only
+/// read when the crate is built, so a newly set variable doesn't trigger a
+/// rebuild.
+///
+/// Requires crate feature `"proc-macro"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let package = env_map!(prefix = "CARGO_PKG_");
+/// assert_eq!(package["CARGO_PKG_NAME"], "maplit");
+///
+/// let build = env_map!{
+///     "CARGO_PKG_VERSION",
+///     "BUILD_CHANNEL" => "dev",
+/// };
+/// # let _ = build;
+/// # }
+/// ```
+pub use maplit_macros::env_map;
+
+#[cfg(feature = "proc-macro")]
+/// Create a **HashMap** from a query string, decoded at compile time.
+///
+/// `querymap!("a=1&b=2&tags=x&tags=y")` maps each key to a
+/// `Vec<String>` of its values, in order. With `@single;` each key maps to
+/// a `String`, and a repeated key is a compile error.
+///
+/// A leading `?` and empty pairs are skipped, and a pair without `=` has an
+/// empty value. Keys and values are decoded like form data: `+` is a space
+/// and `%XX` is a byte in hexadecimal. An invalid escape, or bytes that are
+/// not UTF-8, are compile errors.
+///
+/// Requires crate feature `"proc-macro"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let query = querymap!("?q=rust+macros&tag=a&tag=b%26c");
+/// assert_eq!(query["q"], ["rust macros"]);
+/// assert_eq!(query["tag"], ["a", "b&c"]);
+///
+/// let query = querymap!(@single; "page=2&sort=name");
+/// assert_eq!(query["page"], "2");
+/// # }
+/// ```
+pub use maplit_macros::querymap;
+
+#[cfg(feature = "prehashed")]
+#[macro_export(local_inner_macros)]
+/// Create a **PrehashedMap** from a list of key-value pairs, hashing the
+/// keys at compile time.
+///
+/// The map is a hashbrown `HashMap` with the deterministic
+/// [`FnvHasher`](struct.FnvHasher.html), so each key's hash can be computed
+/// by the macro and the entries are inserted without hashing at runtime.
+/// This speeds up building large constant tables. Lookups hash as usual.
+///
+/// The keys must be string, char or bool literals, or integer literals with
+/// a type suffix like `1u32`.
+///
+/// Requires crate feature `"prehashed"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let map = prehashed_map!{
+///     "a" => 1,
+///     "b" => 2,
+/// };
+/// assert_eq!(map["a"], 1);
+/// assert_eq!(map.get("c"), None);
+///
+/// let map = prehashed_map!{-1i64 => 'a', 7i64 => 'b'};
+/// assert_eq!(map[&-1], 'a');
+/// # }
+/// ```
+macro_rules! prehashed_map {
+    ($($tt:tt)*) => { $crate::__prehashed_map!($crate; $($tt)*) };
+}
+
+#[cfg(feature = "proc-macro")]
+#[macro_export(local_inner_macros)]
+/// Create a **PerfectMap** from a list of key-value pairs, with a perfect
+/// hash function found at compile time.
+///
+/// A lookup in a [`PerfectMap`](struct.PerfectMap.html) hashes the key twice
+/// and compares it with a single entry, and the map needs no allocation, so
+/// it can be a `static`. The entries must then be constant expressions.
+///
+/// The keys must be string, char or bool literals, or integer literals with
+/// a type suffix like `1u32`, and it is a compile error if a key is
+/// repeated. As in the [`checked`](checked/index.html) macros, an entry at
+/// the end of a line doesn't need a comma.
+///
+/// Requires crate feature `"proc-macro"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// use maplit::PerfectMap;
+///
+/// static COLORS: PerfectMap<&str, u32> = perfectmap!{
+///     "red" => 0xff0000,
+///     "green" => 0x00ff00,
+///     "blue" => 0x0000ff,
+/// };
+///
+/// # fn main() {
+/// assert_eq!(COLORS.get("green"), Some(&0x00ff00));
+/// assert_eq!(COLORS.get("pink"), None);
+/// assert_eq!(COLORS.len(), 3);
+/// # }
+/// ```
+macro_rules! perfectmap {
+    ($($tt:tt)*) => { $crate::__perfectmap!($crate; $($tt)*) };
+}
+
+#[cfg(feature = "proc-macro")]
+#[macro_export(local_inner_macros)]
+/// Create a typed configuration with defaults that environment variables
+/// override at runtime.
+///
+/// Each setting is `"key": Type => default`, and the configuration has a
+/// method `key()` that returns the setting as a `Type`. The method parses
+/// the environment variable named after the key in upper case, with an
+/// optional `prefix = "APP_";` before it, and returns the default if the
+/// variable is not set. `to_map()` returns all settings as a
+/// `HashMap<&'static str, String>`.
+///
+/// The keys must be identifiers, and the types must implement `FromStr` and
+/// `Display`. A string literal default is converted with `From<&str>`, so
+/// it can be the default of a `String` setting.
+///
+/// The variables are read each time a method is called. A method panics if
+/// its variable is set but can't be parsed as the setting's type.
+///
+/// Requires crate feature `"proc-macro"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let config = config!{
+///     prefix = "APP_";
+///     "port": u16 => 8080,
+///     "host": String => "0.0.0.0",
+/// };
+/// // Reads `APP_PORT`, which is not set.
+/// assert_eq!(config.port(), 8080);
+/// assert_eq!(config.to_map()["host"], "0.0.0.0");
+/// # }
+/// ```
+macro_rules! config {
+    ($($tt:tt)*) => { $crate::__config!($crate; $($tt)*) };
+}
+
+#[cfg(feature = "proc-macro")]
+#[macro_export(local_inner_macros)]
+/// Create a `HashMap<String, Value>` tree, with identifier keys and nested
+/// maps.
+///
+/// Each entry is `key => value`, or `key { ... }` for a nested map. A key is
+/// an identifier, which is used as a string, or a string literal. A value is
+/// converted with [`Value::from`](enum.Value.html), and a nested map becomes
+/// a `Value::Map`. The comma after a nested map or at the end of a line is
+/// optional. It is a compile error if a key is repeated in the same map.
+///
+/// Requires crate feature `"proc-macro"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// use maplit::Value;
+///
+/// # fn main() {
+/// let config = dict!{
+///     server {
+///         port => 8080,
+///         tls { cert => "x" }
+///         "max-connections" => 100,
+///     }
+///     debug => true,
+/// };
+/// assert_eq!(config["debug"], Value::Bool(true));
+/// let server = &config["server"];
+/// assert_eq!(server.get("port"), Some(&Value::Int(8080)));
+/// assert_eq!(server.get("tls").and_then(|tls| tls.get("cert")), Some(&Value::from("x")));
+/// # }
+/// ```
+macro_rules! dict {
+    ($($tt:tt)*) => { $crate::__dict!($crate; $($tt)*) };
+}
+
+#[macro_export(local_inner_macros)]
+/// Create a **HashMap** from a list of key-value pairs
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let map = hashmap!{
+///     "a" => 1,
+///     "b" => 2,
+/// };
+/// assert_eq!(map["a"], 1);
+/// assert_eq!(map["b"], 2);
+/// assert_eq!(map.get("c"), None);
+/// # }
+/// ```
+///
+/// ## Default values
+///
+/// A value written as `@default` is replaced by `Default::default()`, with
+/// the type of the map's other values. An expression named `default`, like
+/// a local variable, is used as it is.
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let settings = hashmap!{
+///     "retries" => 3,
+///     "timeout" => @default,
+/// };
+/// assert_eq!(settings["timeout"], 0);
+/// # }
+/// ```
+///
+/// For a map with enum keys, a last entry `..value` maps every variant that
+/// isn't listed to `value`, like struct update syntax. The value is
+/// evaluated once for each of those variants, which are listed by `strum`'s
+/// `IntoEnumIterator`, so the enum must derive `EnumIter`. This needs crate
+/// feature `"strum"`.
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// extern crate strum;
+/// use strum::EnumIter;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, EnumIter)]
+/// enum Color { Red, Green, Blue }
+///
+/// # #[cfg(feature = "strum")]
+/// # fn main() {
+/// let weights = hashmap!{
+///     Color::Red => 2.5,
+///     ..Default::default()
+/// };
+/// assert_eq!(weights.len(), 3);
+/// assert_eq!(weights[&Color::Blue], 0.0);
+/// # }
+/// # #[cfg(not(feature = "strum"))]
+/// # fn main() {}
+/// ```
+///
+/// ## Value modifiers
+///
+/// A modifier before the entries wraps every value: `@arc;` uses `Arc::new`,
+/// `@rc;` uses `Rc::new`, `@cell;` uses `Cell::new`, `@refcell;` uses
+/// `RefCell::new`, `@mutex;` uses `Mutex::new` and `@rwlock;` uses
+/// `RwLock::new`. The last two give each entry its own lock.
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let map = hashmap!{@arc;
+///     "a" => vec![1, 2],
+///     "b" => vec![3],
+/// };
+/// let shared = map["a"].clone();
+/// assert_eq!(*shared, [1, 2]);
+///
+/// let counters = hashmap!{@mutex; "hits" => 0, "misses" => 0};
+/// *counters["hits"].lock().unwrap() += 1;
+/// assert_eq!(*counters["hits"].lock().unwrap(), 1);
+/// # }
+/// ```
+///
+/// `@boxed dyn Trait;` boxes every value and coerces it to the trait object
+/// type, so values of different types can share one map.
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// use std::fmt::Display;
+///
+/// let map = hashmap!{@boxed dyn Display;
+///     "int" => 1,
+///     "str" => "two",
+/// };
+/// assert_eq!(map["int"].to_string(), "1");
+/// assert_eq!(map["str"].to_string(), "two");
+/// # }
+/// ```
+///
+/// ## Key modifiers
+///
+/// `@keys cow;` converts every key to `Cow<'static, str>` with `Cow::from`,
+/// so string literals are borrowed and `String` keys are owned.
+///
+/// `@keys arcstr;`, `@keys rcstr;` and `@keys boxstr;` convert every key to
+/// `Arc<str>`, `Rc<str>` or `Box<str>`, which have no spare capacity like
+/// `String` does, and are cheap to clone in the case of `Arc` and `Rc`.
+///
+/// `@keys path;` and `@keys osstring;` convert every key to `PathBuf` or
+/// `OsString`. See also [`pathkeyed!`](macro.pathkeyed.html).
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// use std::borrow::Cow;
+///
+/// let suffix = 2;
+/// let map = hashmap!{@keys cow;
+///     "a" => 1,
+///     format!("b{}", suffix) => 2,
+/// };
+/// assert!(matches!(map.keys().find(|k| *k == "a"), Some(Cow::Borrowed(_))));
+/// assert_eq!(map["b2"], 2);
+///
+/// let map = hashmap!{@keys arcstr; "a" => 1};
+/// let key: std::sync::Arc<str> = map.keys().next().unwrap().clone();
+/// assert_eq!(&*key, "a");
+/// # }
+/// ```
+///
+/// ## Expansion
+///
+/// By default the macro creates the map with the capacity for the entries,
+/// evaluates them in order in arrays of up to 256, and passes each array as
+/// a slice to a shared function that inserts the entries. The function is
+/// compiled once per map type rather than inlined at each call site, and a
+/// generated literal with thousands of entries doesn't expand to one huge
+/// array. With `@from_iter;` the map is instead collected from a single
+/// array of `(key, value)` tuples with `FromIterator`, which needs Rust
+/// 1.53 or later, for arrays that iterate by value.
+///
+/// `@reserve n;` makes room for `n` more entries than the literal has, for
+/// a map that grows right after it is built. This form doesn't support
+/// `@default` values.
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let map = hashmap!{@reserve 100;
+///     "a" => 1,
+///     "b" => 2,
+/// };
+/// assert!(map.capacity() >= 102);
+/// # }
+/// ```
+///
+/// `@from_array;` expands to a single `HashMap::from([...])` call instead,
+/// which needs Rust 1.56 or later. [`hashset!`](macro.hashset.html)
+/// supports it too. Like `@from_iter;`, it builds one array of all the
+/// entries, so it is meant for literals of modest size.
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let map = hashmap!{@from_iter;
+///     "a" => 1,
+///     "b" => 2,
+/// };
+/// assert_eq!(map["b"], 2);
+///
+/// let map = hashmap!{@from_array; "a" => 1, "b" => 2};
+/// assert_eq!(map["a"], 1);
+/// # }
+/// ```
+macro_rules! hashmap {
+    (@count $($rest:expr),*) => (__count!($($rest)*));
+    (@wrap arc $value:expr) => (::std::sync::Arc::new($value));
+    (@wrap rc $value:expr) => (::std::rc::Rc::new($value));
+    (@wrap refcell $value:expr) => (::std::cell::RefCell::new($value));
+    (@wrap cell $value:expr) => (::std::cell::Cell::new($value));
+    (@wrap mutex $value:expr) => (::std::sync::Mutex::new($value));
+    (@wrap rwlock $value:expr) => (::std::sync::RwLock::new($value));
+    (@key cow $key:expr) => (::std::borrow::Cow::<'static, str>::from($key));
+    (@key arcstr $key:expr) => (::std::sync::Arc::<str>::from($key));
+    (@key rcstr $key:expr) => (::std::rc::Rc::<str>::from($key));
+    (@key boxstr $key:expr) => (::std::boxed::Box::<str>::from($key));
+    (@key path $key:expr) => (::std::path::PathBuf::from($key));
+    (@key osstring $key:expr) => (::std::ffi::OsString::from($key));
+
+    (@from_iter; $($key:expr => $value:expr),* $(,)*) => {
+        <::std::collections::HashMap<_, _> as ::std::iter::FromIterator<_>>::from_iter(
+            [$(($key, $value)),*])
+    };
+    (@from_array; $($key:expr => $value:expr),* $(,)*) => {
+        ::std::collections::HashMap::from([$(($key, $value)),*])
+    };
+    (@reserve $extra:expr; $($key:expr => $value:expr),* $(,)*) => {
+        {
+            let _cap = hashmap!(@count $($key),*) + $extra;
+            let mut _map = ::std::collections::HashMap::with_capacity(_cap);
+            __insert_chunked!(__insert_all _map (8 7 6 5 4 3 2 1)
+                              $([::std::option::Option::Some(($key, $value)),])*);
+            _map
+        }
+    };
+    (@keys $conv:ident; $($key:expr => $value:expr),* $(,)*) => {
+        hashmap!(@build $(hashmap!(@key $conv $key) => $value),*)
+    };
+    (@boxed $tr:ty; $($key:expr => $value:expr),* $(,)*) => {
+        hashmap!(@build $($key => ::std::boxed::Box::new($value) as ::std::boxed::Box<$tr>),*)
+    };
+    (@$wrap:ident; $($key:expr => $value:expr),* $(,)*) => {
+        hashmap!(@build $($key => hashmap!(@wrap $wrap $value)),*)
+    };
+    // Replace `@default` values with `Default::default()`, taking up to four
+    // entries per step to keep the recursion depth down.
+    (@defaults [$($done:tt)*] $(,)*) => { hashmap!(@build $($done)*) };
+    (@defaults [$($done:tt)*] .. $fill:expr $(,)*) => { hashmap!(@fill $fill; $($done)*) };
+    (@defaults [$($done:tt)*] $k1:expr => @default, $($rest:tt)*) => {
+        hashmap!(@defaults [$($done)* $k1 => ::std::default::Default::default(),] $($rest)*)
+    };
+    (@defaults [$($done:tt)*] $k1:expr => $v1:expr, $k2:expr => @default, $($rest:tt)*) => {
+        hashmap!(@defaults [$($done)* $k1 => $v1, $k2 => ::std::default::Default::default(),] $($rest)*)
+    };
+    (@defaults [$($done:tt)*] $k1:expr => $v1:expr, $k2:expr => $v2:expr,
+     $k3:expr => @default, $($rest:tt)*) => {
+        hashmap!(@defaults [$($done)* $k1 => $v1, $k2 => $v2, $k3 => ::std::default::Default::default(),] $($rest)*)
+    };
+    (@defaults [$($done:tt)*] $k1:expr => $v1:expr, $k2:expr => $v2:expr,
+     $k3:expr => $v3:expr, $k4:expr => @default, $($rest:tt)*) => {
+        hashmap!(@defaults [$($done)* $k1 => $v1, $k2 => $v2, $k3 => $v3, $k4 => ::std::default::Default::default(),] $($rest)*)
+    };
+    (@defaults [$($done:tt)*] $k1:expr => $v1:expr, $k2:expr => $v2:expr,
+     $k3:expr => $v3:expr, $k4:expr => $v4:expr, $($rest:tt)*) => {
+        hashmap!(@defaults [$($done)* $k1 => $v1, $k2 => $v2, $k3 => $v3, $k4 => $v4,] $($rest)*)
+    };
+    (@defaults [$($done:tt)*] $k1:expr => $v1:expr, $($rest:tt)*) => {
+        hashmap!(@defaults [$($done)* $k1 => $v1,] $($rest)*)
+    };
+    (@defaults [$($done:tt)*] $($rest:tt)*) => {
+        ::std::compile_error!("expected a list of `key => value` entries")
+    };
+
+    (@fill $fill:expr; $($key:expr => $value:expr),* $(,)*) => {
+        {
+            let mut _map = hashmap!(@build $($key => $value),*);
+            __hashmap_fill!(_map, $fill);
+            _map
+        }
+    };
+    (@build $($key:expr => $value:expr),* $(,)*) => {
+        {
+            let _cap = hashmap!(@count $($key),*);
+            let mut _map = ::std::collections::HashMap::with_capacity(_cap);
+            __insert_chunked!(__insert_all _map (8 7 6 5 4 3 2 1)
+                              $([::std::option::Option::Some(($key, $value)),])*);
+            _map
+        }
+    };
+    ($($key:expr => $value:expr),* $(,)*) => { hashmap!(@build $($key => $value),*) };
+    // Only a literal with `@default` values or a `..value` entry gets here:
+    // `@` can't start a value expression, and `..value` has no `=>`.
+    ($($rest:tt)*) => { hashmap!(@defaults [] $($rest)* ,) };
+}
+
+/// Count the token trees, halving them at each step, so that the recursion
+/// depth is logarithmic in their number.
+#[doc(hidden)]
+#[macro_export(local_inner_macros)]
+macro_rules! __count {
+    () => (0usize);
+    ($odd:tt $($a:tt $b:tt)*) => ((__count!($($a)*) << 1) | 1);
+    ($($a:tt $even:tt)*) => (__count!($($a)*) << 1);
+}
+
+/// Pass the bracketed items to `$crate::$helper` in slices of up to 256,
+/// by merging the brackets pairwise once for each token in the leading
+/// group.
+#[doc(hidden)]
+#[macro_export(local_inner_macros)]
+macro_rules! __insert_chunked {
+    ($helper:ident $target:ident () $([$($item:tt)*])*) => {
+        $(
+            $crate::$helper(&mut $target, &mut [$($item)*]);
+        )*
+    };
+    ($helper:ident $target:ident ($level:tt $($levels:tt)*)
+     $odd:tt $([$($a:tt)*] [$($b:tt)*])*) => {
+        __insert_chunked!($helper $target ($($levels)*) $odd $([$($a)* $($b)*])*)
+    };
+    ($helper:ident $target:ident ($level:tt $($levels:tt)*) $([$($a:tt)*] [$($b:tt)*])*) => {
+        __insert_chunked!($helper $target ($($levels)*) $([$($a)* $($b)*])*)
+    };
+}
+
+#[cfg(feature = "strum")]
+#[doc(hidden)]
+#[macro_export(local_inner_macros)]
+macro_rules! __hashmap_fill {
+    ($map:ident, $fill:expr) => { $crate::__fill_variants(&mut $map, || $fill) };
+}
+
+#[cfg(not(feature = "strum"))]
+#[doc(hidden)]
+#[macro_export(local_inner_macros)]
+macro_rules! __hashmap_fill {
+    ($map:ident, $fill:expr) => {
+        ::std::compile_error!("`..value` entries in `hashmap!` need crate feature \"strum\"")
+    };
+}
+
+/// Insert `fill()` for every variant of `E` that isn't a key of `map`.
+#[cfg(feature = "strum")]
+#[doc(hidden)]
+pub fn __fill_variants<E, V, S, F>(map: &mut ::std::collections::HashMap<E, V, S>, mut fill: F)
+    where E: __strum::IntoEnumIterator + ::std::hash::Hash + Eq,
+          S: ::std::hash::BuildHasher,
+          F: FnMut() -> V,
+{
+    for variant in E::iter() {
+        let _ = map.entry(variant).or_insert_with(&mut fill);
+    }
+}
+
+/// Create a **HashSet** from a list of elements.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let set = hashset!{"a", "b"};
+/// assert!(set.contains("a"));
+/// assert!(set.contains("b"));
+/// assert!(!set.contains("c"));
+/// # }
+/// ```
+///
+/// ## Generated elements
+///
+/// Like `vec![elem; n]`, `hashset![elem; n]` evaluates `elem` `n` times and
+/// inserts each result, in a set with capacity for `n` elements.
+/// `hashset![elem; for pat in iter]` evaluates `elem` once for each item of
+/// `iter`, bound to `pat`, with capacity for the iterator's lower size
+/// bound. Equal elements are only inserted once, so the set can have fewer
+/// than `n` elements.
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let mut next = 0;
+/// let ids = hashset![{ next += 1; next }; 100];
+/// assert_eq!(ids.len(), 100);
+///
+/// let squares = hashset![i * i; for i in -3..4];
+/// assert_eq!(squares, hashset!{0, 1, 4, 9});
+/// # }
+/// ```
+///
+/// `hashset!{@from_array; elem, ...}` expands to `HashSet::from([...])`,
+/// like [`hashmap!`](macro.hashmap.html)'s `@from_array;`.
+#[macro_export(local_inner_macros)]
+macro_rules! hashset {
+    (@count $($rest:expr),*) => (__count!($($rest)*));
+    (@from_array; $($key:expr),* $(,)*) => {
+        ::std::collections::HashSet::from([$($key),*])
+    };
+
+    ($elem:expr; for $pat:pat in $iter:expr) => {
+        {
+            let _iter = ::std::iter::IntoIterator::into_iter($iter);
+            let mut _set = ::std::collections::HashSet::with_capacity(
+                ::std::iter::Iterator::size_hint(&_iter).0);
+            for $pat in _iter {
+                let _ = _set.insert($elem);
+            }
+            _set
+        }
+    };
+    ($elem:expr; $n:expr) => {
+        {
+            let _n: usize = $n;
+            let mut _set = ::std::collections::HashSet::with_capacity(_n);
+            for _ in 0.._n {
+                let _ = _set.insert($elem);
+            }
+            _set
+        }
+    };
+
+    (@build $($key:expr),*) => {
+        {
+            let _cap = hashset!(@count $($key),*);
+            let mut _set = ::std::collections::HashSet::with_capacity(_cap);
+            __insert_chunked!(__add_all _set (8 7 6 5 4 3 2 1)
+                              $([::std::option::Option::Some($key),])*);
+            _set
+        }
+    };
+    ($($key:expr,)+) => { hashset!($($key),+) };
+    ($($key:expr),*) => { hashset!(@build $($key),*) };
+}
+
+#[macro_export(local_inner_macros)]
+/// Create a **BTreeMap** from a list of key-value pairs
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let map = btreemap!{
+///     "a" => 1,
+///     "b" => 2,
+/// };
+/// assert_eq!(map["a"], 1);
+/// assert_eq!(map["b"], 2);
+/// assert_eq!(map.get("c"), None);
+/// # }
+/// ```
+///
+/// The same `@default` values, value and key modifiers and `@from_iter;`
+/// expansion as for [`hashmap!`](macro.hashmap.html) are supported.
+///
+/// ## Sorted entries
+///
+/// With `@sorted;` the entries must be listed in strictly increasing key
+/// order, which is checked with a debug assertion. This is only a check
+/// that the literal is kept sorted: the map is collected with
+/// `FromIterator`, which sorts the entries again, in a single pass when
+/// they are already in order. Like `@from_iter;`, this needs Rust 1.53 or
+/// later.
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let map = btreemap!{@sorted;
+///     "a" => 1,
+///     "b" => 2,
+///     "c" => 3,
+/// };
+/// assert_eq!(map["b"], 2);
+/// # }
+/// ```
+macro_rules! btreemap {
+    (@wrap arc $value:expr) => (::std::sync::Arc::new($value));
+    (@wrap rc $value:expr) => (::std::rc::Rc::new($value));
+    (@wrap refcell $value:expr) => (::std::cell::RefCell::new($value));
+    (@wrap cell $value:expr) => (::std::cell::Cell::new($value));
+    (@wrap mutex $value:expr) => (::std::sync::Mutex::new($value));
+    (@wrap rwlock $value:expr) => (::std::sync::RwLock::new($value));
+    (@key cow $key:expr) => (::std::borrow::Cow::<'static, str>::from($key));
+    (@key arcstr $key:expr) => (::std::sync::Arc::<str>::from($key));
+    (@key rcstr $key:expr) => (::std::rc::Rc::<str>::from($key));
+    (@key boxstr $key:expr) => (::std::boxed::Box::<str>::from($key));
+    (@key path $key:expr) => (::std::path::PathBuf::from($key));
+    (@key osstring $key:expr) => (::std::ffi::OsString::from($key));
+
+    (@from_iter; $($key:expr => $value:expr),* $(,)*) => {
+        <::std::collections::BTreeMap<_, _> as ::std::iter::FromIterator<_>>::from_iter(
+            [$(($key, $value)),*])
+    };
+    (@sorted; $($key:expr => $value:expr),* $(,)*) => {
+        {
+            let _entries = [$(($key, $value)),*];
+            ::std::debug_assert!($crate::__keys_increasing(&_entries),
+                                 "btreemap!: @sorted keys are not in increasing order");
+            <::std::collections::BTreeMap<_, _> as ::std::iter::FromIterator<_>>::from_iter(
+                _entries)
+        }
+    };
+    (@keys $conv:ident; $($key:expr => $value:expr),* $(,)*) => {
+        btreemap!(@build $(btreemap!(@key $conv $key) => $value),*)
+    };
+    (@boxed $tr:ty; $($key:expr => $value:expr),* $(,)*) => {
+        btreemap!(@build $($key => ::std::boxed::Box::new($value) as ::std::boxed::Box<$tr>),*)
+    };
+    (@$wrap:ident; $($key:expr => $value:expr),* $(,)*) => {
+        btreemap!(@build $($key => btreemap!(@wrap $wrap $value)),*)
+    };
+    // Replace `@default` values with `Default::default()`, taking up to four
+    // entries per step to keep the recursion depth down.
+    (@defaults [$($done:tt)*] $(,)*) => { btreemap!(@build $($done)*) };
+    (@defaults [$($done:tt)*] $k1:expr => @default, $($rest:tt)*) => {
+        btreemap!(@defaults [$($done)* $k1 => ::std::default::Default::default(),] $($rest)*)
+    };
+    (@defaults [$($done:tt)*] $k1:expr => $v1:expr, $k2:expr => @default, $($rest:tt)*) => {
+        btreemap!(@defaults [$($done)* $k1 => $v1, $k2 => ::std::default::Default::default(),] $($rest)*)
+    };
+    (@defaults [$($done:tt)*] $k1:expr => $v1:expr, $k2:expr => $v2:expr,
+     $k3:expr => @default, $($rest:tt)*) => {
+        btreemap!(@defaults [$($done)* $k1 => $v1, $k2 => $v2, $k3 => ::std::default::Default::default(),] $($rest)*)
+    };
+    (@defaults [$($done:tt)*] $k1:expr => $v1:expr, $k2:expr => $v2:expr,
+     $k3:expr => $v3:expr, $k4:expr => @default, $($rest:tt)*) => {
+        btreemap!(@defaults [$($done)* $k1 => $v1, $k2 => $v2, $k3 => $v3, $k4 => ::std::default::Default::default(),] $($rest)*)
+    };
+    (@defaults [$($done:tt)*] $k1:expr => $v1:expr, $k2:expr => $v2:expr,
+     $k3:expr => $v3:expr, $k4:expr => $v4:expr, $($rest:tt)*) => {
+        btreemap!(@defaults [$($done)* $k1 => $v1, $k2 => $v2, $k3 => $v3, $k4 => $v4,] $($rest)*)
+    };
+    (@defaults [$($done:tt)*] $k1:expr => $v1:expr, $($rest:tt)*) => {
+        btreemap!(@defaults [$($done)* $k1 => $v1,] $($rest)*)
+    };
+    (@defaults [$($done:tt)*] $($rest:tt)*) => {
+        ::std::compile_error!("expected a list of `key => value` entries")
+    };
+
+    (@build $($key:expr => $value:expr),* $(,)*) => {
+        {
+            let mut _map = ::std::collections::BTreeMap::new();
+            __insert_chunked!(__insert_all _map (8 7 6 5 4 3 2 1)
+                              $([::std::option::Option::Some(($key, $value)),])*);
+            _map
+        }
+    };
+    ($($key:expr => $value:expr),* $(,)*) => { btreemap!(@build $($key => $value),*) };
+    // Only a literal with `@default` values gets here, since `@` can't start
+    // the value expression of the arm above.
+    ($($rest:tt)*) => { btreemap!(@defaults [] $($rest)* ,) };
+}
+
+/// Return `true` if the keys of `entries` are strictly increasing.
+#[doc(hidden)]
+pub fn __keys_increasing<K: Ord, V>(entries: &[(K, V)]) -> bool {
+    entries.windows(2).all(|w| w[0].0 < w[1].0)
+}
+
+#[macro_export(local_inner_macros)]
+/// Create a **BTreeSet** from a list of elements.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let set = btreeset!{"a", "b"};
+/// assert!(set.contains("a"));
+/// assert!(set.contains("b"));
+/// assert!(!set.contains("c"));
+/// # }
+/// ```
+///
+/// ## Expansion
+///
+/// The elements are collected into a vector, in order, and the set is built
+/// from it with `FromIterator`. The standard library of recent compilers
+/// then sorts the elements once and builds the tree in bulk, which for
+/// 10,000 string elements takes about half the time of inserting them one
+/// by one, and a tenth of it when they are listed in sorted order. Older
+/// compilers insert them one by one, as fast as before.
+macro_rules! btreeset {
+    (@count $($rest:expr),*) => (__count!($($rest)*));
+
+    ($($key:expr,)+) => (btreeset!($($key),+));
+
+    ( $($key:expr),* ) => {
+        {
+            let _cap = btreeset!(@count $($key),*);
+            let mut _elems = ::std::vec::Vec::with_capacity(_cap);
+            $(
+                _elems.push($key);
+            )*
+            <::std::collections::BTreeSet<_> as ::std::iter::FromIterator<_>>::from_iter(_elems)
+        }
+    };
+}
+
+#[cfg(feature = "enum-map")]
+#[doc(hidden)]
+pub extern crate enum_map as __enum_map;
+
+#[cfg(feature = "enum-map")]
+#[macro_export(local_inner_macros)]
+/// Create an **EnumMap** (from the `enum-map` crate) from a list of
+/// variant-value pairs.
+///
+/// The keys are variants. Each value is evaluated once, in the order of the
+/// entries. The variants are also listed in an exhaustive `match`, so
+/// leaving one out is a compile error, and listing one twice panics.
+///
+/// Requires crate feature `"enum-map"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// #[macro_use] extern crate enum_map;
+/// # fn main() {
+///
+/// #[derive(Debug, Enum)]
+/// enum Color { Red, Green, Blue }
+///
+/// let map = enummap!{
+///     Color::Red => "#f00",
+///     Color::Green => "#0f0",
+///     Color::Blue => "#00f",
+/// };
+/// assert_eq!(map[Color::Red], "#f00");
+/// assert_eq!(map[Color::Blue], "#00f");
+/// # }
+/// ```
+///
+/// ```should_panic
+/// #[macro_use] extern crate maplit;
+/// #[macro_use] extern crate enum_map;
+/// # fn main() {
+///
+/// #[derive(Debug, Enum)]
+/// enum Color { Red, Green }
+///
+/// let map = enummap!{
+///     Color::Red => 1,
+///     Color::Green => 2,
+///     Color::Red => 3,
+/// };
+/// # }
+/// ```
+macro_rules! enummap {
+    ($($key:path => $value:expr,)+) => (enummap!($($key => $value),+));
+
+    ( $($key:path => $value:expr),* ) => {
+        {
+            let mut _values = $crate::__enum_map::EnumMap::from_fn(|_| ::std::option::Option::None);
+            $(
+                if _values[$key].replace($value).is_some() {
+                    ::std::panic!("enummap!: `{}` is listed twice", ::std::stringify!($key));
+                }
+            )*
+            // The match is exhaustive, so every slot has been filled.
+            let _map = _values.map(|_key, _value| {
+                match _key {
+                    $(
+                        $key => {}
+                    )*
+                }
+                _value.unwrap()
+            });
+            _map
+        }
+    };
+}
+
+#[cfg(feature = "strum")]
+#[doc(hidden)]
+pub extern crate strum as __strum;
+
+#[cfg(feature = "strum")]
+#[macro_export(local_inner_macros)]
+/// Create a **HashMap** with an entry for every variant of an enum.
+///
+/// `map_from_variants!(Enum, f)` maps each variant to `f(&variant)`. The
+/// variants are listed by `strum`'s `IntoEnumIterator`, so the enum must
+/// derive `EnumIter`, and the map covers variants added later without
+/// changes.
+///
+/// Requires crate feature `"strum"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// extern crate strum;
+/// use strum::EnumIter;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, EnumIter)]
+/// enum Color { Red, Green, Blue }
+///
+/// impl Color {
+///     fn hex(&self) -> &'static str {
+///         match *self {
+///             Color::Red => "#f00",
+///             Color::Green => "#0f0",
+///             Color::Blue => "#00f",
+///         }
+///     }
+/// }
+///
+/// # fn main() {
+/// let map = map_from_variants!(Color, |c| c.hex());
+/// assert_eq!(map.len(), 3);
+/// assert_eq!(map[&Color::Green], "#0f0");
+/// # }
+/// ```
+macro_rules! map_from_variants {
+    ($enum_type:ty, $f:expr,) => (map_from_variants!($enum_type, $f));
+    ($enum_type:ty, $f:expr) => {
+        $crate::__map_from_variants::<$enum_type, _, _>($f)
+    };
+}
+
+/// Map every variant of `E` to `f(&variant)`.
+#[cfg(feature = "strum")]
+#[doc(hidden)]
+pub fn __map_from_variants<E, V, F>(mut f: F) -> ::std::collections::HashMap<E, V>
+    where E: __strum::IntoEnumIterator + ::std::hash::Hash + Eq,
+          F: FnMut(&E) -> V,
+{
+    E::iter().map(|e| {
+        let value = f(&e);
+        (e, value)
+    }).collect()
+}
+
+#[cfg(feature = "hashlink")]
+#[doc(hidden)]
+pub extern crate hashlink as __hashlink;
+
+#[cfg(feature = "hashlink")]
+#[macro_export(local_inner_macros)]
+/// Create a **LinkedHashMap** (from the `hashlink` crate) from a list of
+/// key-value pairs.
+///
+/// Iteration order follows the order the entries are written in.
+///
+/// Requires crate feature `"hashlink"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let map = linkedhashmap!{
+///     "b" => 2,
+///     "a" => 1,
+///     "c" => 3,
+/// };
+/// assert_eq!(map["a"], 1);
+/// let keys: Vec<_> = map.keys().cloned().collect();
+/// assert_eq!(keys, ["b", "a", "c"]);
+/// # }
+/// ```
+macro_rules! linkedhashmap {
+    (@count $($rest:expr),*) => (__count!($($rest)*));
+
+    ($($key:expr => $value:expr,)+) => { linkedhashmap!($($key => $value),+) };
+    ($($key:expr => $value:expr),*) => {
+        {
+            let _cap = linkedhashmap!(@count $($key),*);
+            let mut _map = $crate::__hashlink::LinkedHashMap::with_capacity(_cap);
+            $(
+                let _ = _map.insert($key, $value);
+            )*
+            _map
+        }
+    };
+}
+
+#[cfg(feature = "lru")]
+#[doc(hidden)]
+pub extern crate lru as __lru;
+
+#[cfg(feature = "lru")]
+#[macro_export(local_inner_macros)]
+/// Create an **LruCache** (from the `lru` crate) with the given capacity,
+/// pre-warmed with a list of key-value pairs.
+///
+/// The entries are put into the cache in the order they are written, so the
+/// last entry is the most recently used one.
+///
+/// **Panics** if the capacity is zero.
+///
+/// Requires crate feature `"lru"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let mut cache = lru!(cap = 2;
+///     "a" => 1,
+///     "b" => 2,
+///     "c" => 3,
+/// );
+/// assert_eq!(cache.len(), 2);
+/// assert_eq!(cache.get("a"), None);
+/// assert_eq!(cache.get("c"), Some(&3));
+/// # }
+/// ```
+macro_rules! lru {
+    (cap = $cap:expr; $($key:expr => $value:expr,)+) => (lru!(cap = $cap; $($key => $value),+));
+
+    (cap = $cap:expr; $($key:expr => $value:expr),*) => {
+        {
+            let _cap = ::std::num::NonZeroUsize::new($cap)
+                .expect("lru!: capacity must be non-zero");
+            let mut _cache = $crate::__lru::LruCache::new(_cap);
+            $(
+                let _ = _cache.put($key, $value);
+            )*
+            _cache
+        }
+    };
+}
+
+#[cfg(feature = "flurry")]
+#[doc(hidden)]
+pub extern crate flurry as __flurry;
+
+#[cfg(feature = "flurry")]
+#[macro_export(local_inner_macros)]
+/// Create a concurrent **HashMap** (from the `flurry` crate) from a list of
+/// key-value pairs.
+///
+/// The epoch guard needed for insertion is taken and released inside the
+/// macro.
+///
+/// Requires crate feature `"flurry"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let map = flurryhashmap!{
+///     "a" => 1,
+///     "b" => 2,
+/// };
+/// let map = map.pin();
+/// assert_eq!(map.get("a"), Some(&1));
+/// assert_eq!(map.get("c"), None);
+/// # }
+/// ```
+macro_rules! flurryhashmap {
+    (@count $($rest:expr),*) => (__count!($($rest)*));
+
+    ($($key:expr => $value:expr,)+) => { flurryhashmap!($($key => $value),+) };
+    ($($key:expr => $value:expr),*) => {
+        {
+            let _cap = flurryhashmap!(@count $($key),*);
+            let _map = $crate::__flurry::HashMap::with_capacity(_cap);
+            {
+                let _pinned = _map.pin();
+                $(
+                    let _ = _pinned.insert($key, $value);
+                )*
+            }
+            _map
+        }
+    };
+}
+
+#[cfg(feature = "evmap")]
+#[doc(hidden)]
+pub extern crate evmap as __evmap;
+
+#[cfg(feature = "evmap")]
+#[macro_export(local_inner_macros)]
+/// Create an **evmap** write and read handle pair from a list of key-value
+/// pairs.
+///
+/// The entries are published before the handles are returned, so they are
+/// immediately visible to readers. The pair is returned in the same order as
+/// `evmap::new()`: `(write_handle, read_handle)`.
+///
+/// Requires crate feature `"evmap"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let (_w, r) = evmap!{
+///     "a" => 1,
+///     "b" => 2,
+/// };
+/// assert_eq!(r.get_one("a").map(|v| *v), Some(1));
+/// assert!(r.get_one("c").is_none());
+/// # }
+/// ```
+macro_rules! evmap {
+    (@count $($rest:expr),*) => (__count!($($rest)*));
+
+    ($($key:expr => $value:expr,)+) => { evmap!($($key => $value),+) };
+    ($($key:expr => $value:expr),*) => {
+        {
+            let _cap = evmap!(@count $($key),*);
+            let (mut _w, _r) = $crate::__evmap::Options::default()
+                .with_capacity(_cap)
+                .construct();
+            $(
+                let _ = _w.insert($key, $value);
+            )*
+            let _ = _w.publish();
+            (_w, _r)
+        }
+    };
+}
+
+#[cfg(feature = "smallvec")]
+#[macro_export(local_inner_macros)]
+/// Create an **AssocList** from a list of key-value pairs.
+///
+/// A later entry with the same key replaces the earlier one, just like
+/// with `hashmap!`.
+///
+/// Requires crate feature `"smallvec"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let map = alist!{
+///     "a" => 1,
+///     "b" => 2,
+/// };
+/// assert_eq!(map.get("a"), Some(&1));
+/// assert_eq!(map.get("c"), None);
+/// # }
+/// ```
+macro_rules! alist {
+    ($($key:expr => $value:expr,)+) => (alist!($($key => $value),+));
+
+    ( $($key:expr => $value:expr),* ) => {
+        {
+            let mut _map = $crate::AssocList::new();
+            $(
+                let _ = _map.insert($key, $value);
+            )*
+            _map
+        }
+    };
+}
+
+#[cfg(feature = "vec_map")]
+#[doc(hidden)]
+pub extern crate vec_map as __vec_map;
+
+#[cfg(feature = "vec_map")]
+#[macro_export(local_inner_macros)]
+/// Create a **VecMap** (from the `vec_map` crate) from a list of
+/// index-value pairs.
+///
+/// The keys are `usize` indices; the map is stored densely, so this is best
+/// suited for small integer keys.
+///
+/// Requires crate feature `"vec_map"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let map = vecmap!{
+///     0 => "a",
+///     3 => "b",
+/// };
+/// assert_eq!(map[0], "a");
+/// assert_eq!(map[3], "b");
+/// assert_eq!(map.get(1), None);
+/// # }
+/// ```
+macro_rules! vecmap {
+    ($($key:expr => $value:expr,)+) => (vecmap!($($key => $value),+));
+
+    ( $($key:expr => $value:expr),* ) => {
+        {
+            let mut _map = $crate::__vec_map::VecMap::new();
+            $(
+                let _ = _map.insert($key, $value);
+            )*
+            _map
+        }
+    };
+}
+
+#[cfg(feature = "slotmap")]
+#[doc(hidden)]
+pub extern crate slotmap as __slotmap;
+
+#[cfg(feature = "slotmap")]
+#[macro_export(local_inner_macros)]
+/// Create a **SlotMap** (from the `slotmap` crate) from a list of values,
+/// returning the map together with an array of the generated keys.
+///
+/// The keys are in the same order as the values.
+///
+/// Requires crate feature `"slotmap"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let (map, [a, b]) = slotmap!["player", "enemy"];
+/// assert_eq!(map[a], "player");
+/// assert_eq!(map[b], "enemy");
+/// # }
+/// ```
+macro_rules! slotmap {
+    (@count $($rest:expr),*) => (__count!($($rest)*));
+
+    ($($value:expr,)+) => { slotmap!($($value),+) };
+    ($($value:expr),*) => {
+        {
+            let _cap = slotmap!(@count $($value),*);
+            let mut _map = $crate::__slotmap::SlotMap::with_capacity(_cap);
+            let _keys = [$(_map.insert($value)),*];
+            (_map, _keys)
+        }
+    };
+}
+
+#[cfg(feature = "petgraph")]
+#[doc(hidden)]
+pub extern crate petgraph as __petgraph;
+
+#[cfg(feature = "petgraph")]
+#[macro_export(local_inner_macros)]
+/// Create a directed **DiGraphMap** (from the `petgraph` crate) from an
+/// adjacency list.
+///
+/// Each entry is a node followed by a list of the nodes it has edges to.
+/// Edge weights are `()`. Nodes are added in the order they first appear.
+///
+/// Requires crate feature `"petgraph"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let graph = graphmap!{
+///     "a" => ["b", "c"],
+///     "b" => ["c"],
+///     "d" => [],
+/// };
+/// assert_eq!(graph.node_count(), 4);
+/// assert_eq!(graph.edge_count(), 3);
+/// assert!(graph.contains_edge("a", "b"));
+/// assert!(!graph.contains_edge("b", "a"));
+/// # }
+/// ```
+macro_rules! graphmap {
+    ($($node:expr => [$($to:expr),* $(,)*]),* $(,)*) => {
+        {
+            let mut _graph = $crate::__petgraph::graphmap::DiGraphMap::new();
+            $(
+                let _node = _graph.add_node($node);
+                $(
+                    let _ = _graph.add_edge(_node, $to, ());
+                )*
+            )*
+            _graph
+        }
+    };
+}
+
+#[cfg(feature = "petgraph")]
+#[macro_export(local_inner_macros)]
+/// Create an undirected **UnGraphMap** (from the `petgraph` crate) from an
+/// adjacency list.
+///
+/// The syntax is the same as for [`graphmap!`](macro.graphmap.html).
+///
+/// Requires crate feature `"petgraph"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let graph = ungraphmap!{
+///     1 => [2, 3],
+///     2 => [3],
+/// };
+/// assert_eq!(graph.edge_count(), 3);
+/// assert!(graph.contains_edge(2, 1));
+/// # }
+/// ```
+macro_rules! ungraphmap {
+    ($($node:expr => [$($to:expr),* $(,)*]),* $(,)*) => {
+        {
+            let mut _graph = $crate::__petgraph::graphmap::UnGraphMap::new();
+            $(
+                let _node = _graph.add_node($node);
+                $(
+                    let _ = _graph.add_edge(_node, $to, ());
+                )*
+            )*
+            _graph
+        }
+    };
+}
+
+#[cfg(feature = "http")]
+#[doc(hidden)]
+pub extern crate http as __http;
+
+#[cfg(feature = "http")]
+#[macro_export(local_inner_macros)]
+/// Create an **http::HeaderMap** from a list of name-value pairs.
+///
+/// Names and values may be anything that converts with `TryFrom` into
+/// `HeaderName` and `HeaderValue`, for example string slices, `String`, or
+/// the constants in `http::header`. Repeated names are appended, so a name
+/// can have several values.
+///
+/// Returns `Result<HeaderMap, http::Error>`, with an error for the first
+/// invalid name or value.
+///
+/// Requires crate feature `"http"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// extern crate http;
+/// # fn main() {
+///
+/// use http::header::AUTHORIZATION;
+///
+/// let token = String::from("Bearer xyz");
+/// let headers = headermap!{
+///     "content-type" => "application/json",
+///     AUTHORIZATION => token,
+/// }.unwrap();
+/// assert_eq!(headers["content-type"], "application/json");
+/// assert_eq!(headers[AUTHORIZATION], "Bearer xyz");
+///
+/// assert!(headermap!{"bad name" => "x"}.is_err());
+/// # }
+/// ```
+macro_rules! headermap {
+    (@count $($rest:expr),*) => (__count!($($rest)*));
+
+    ($($key:expr => $value:expr,)+) => { headermap!($($key => $value),+) };
+    ($($key:expr => $value:expr),*) => {
+        {
+            let _cap = headermap!(@count $($key),*);
+            let mut _map: $crate::__http::HeaderMap = $crate::__http::HeaderMap::with_capacity(_cap);
+            let mut _result: ::std::result::Result<(), $crate::__http::Error> =
+                ::std::result::Result::Ok(());
+            $(
+                if _result.is_ok() {
+                    _result = $crate::__append_header(&mut _map, $key, $value);
+                }
+            )*
+            _result.map(|()| _map)
+        }
+    };
+}
+
+/// Convert `name` and `value` and append them to `map`.
+///
+/// `headermap!` evaluates each entry in the caller, so that `?` and
+/// `return` in it apply there, and converts it here.
+#[cfg(feature = "http")]
+#[doc(hidden)]
+pub fn __append_header<K, V>(map: &mut __http::HeaderMap, name: K, value: V)
+    -> Result<(), __http::Error>
+    where __http::HeaderName: ::std::convert::TryFrom<K>,
+          __http::HeaderValue: ::std::convert::TryFrom<V>,
+          __http::Error: From<<__http::HeaderName as ::std::convert::TryFrom<K>>::Error>
+              + From<<__http::HeaderValue as ::std::convert::TryFrom<V>>::Error>,
+{
+    use std::convert::TryFrom;
+    let name = __http::HeaderName::try_from(name)?;
+    let value = __http::HeaderValue::try_from(value)?;
+    let _ = map.append(name, value);
+    Ok(())
+}
+
+#[cfg(feature = "serde_json")]
+#[doc(hidden)]
+pub extern crate serde_json as __serde_json;
+
+#[cfg(feature = "serde_json")]
+#[macro_export(local_inner_macros)]
+/// Create a **serde_json::Map** from a list of key-value pairs.
+///
+/// Keys are converted with `Into<String>` and values with
+/// `serde_json::Value::from`; use `json!` for a nested value.
+///
+/// Requires crate feature `"serde_json"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// #[macro_use] extern crate serde_json;
+/// # fn main() {
+///
+/// let map = jsonmap!{
+///     "name" => "maplit",
+///     "stars" => 300,
+///     "tags" => json!(["macro", "literal"]),
+/// };
+/// assert_eq!(map["name"], "maplit");
+/// assert_eq!(map["stars"], 300);
+/// assert_eq!(map["tags"][0], "macro");
+/// # }
+/// ```
+macro_rules! jsonmap {
+    (@count $($rest:expr),*) => (__count!($($rest)*));
+
+    ($($key:expr => $value:expr,)+) => { jsonmap!($($key => $value),+) };
+    ($($key:expr => $value:expr),*) => {
+        {
+            let _cap = jsonmap!(@count $($key),*);
+            let mut _map = $crate::__serde_json::Map::with_capacity(_cap);
+            $(
+                let _ = _map.insert(
+                    ::std::convert::Into::<::std::string::String>::into($key),
+                    $crate::__serde_json::Value::from($value));
+            )*
+            _map
+        }
+    };
+}
+
+#[cfg(feature = "phf")]
+#[doc(hidden)]
+pub extern crate phf as __phf;
+
+#[cfg(feature = "phf")]
+#[macro_export]
+/// Declare a static **phf::Map** (from the `phf` crate) from a list of
+/// key-value pairs.
+///
+/// The perfect hash function is generated at compile time. The keys must be
+/// literals, as required by `phf`.
+///
+/// Requires crate feature `"phf"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// extern crate phf;
+///
+/// static_phf_map!(static KEYWORDS: phf::Map<&'static str, u32> = {
+///     "loop" => 1,
+///     "continue" => 2,
+/// });
+///
+/// # fn main() {
+/// assert_eq!(KEYWORDS.get("loop"), Some(&1));
+/// assert_eq!(KEYWORDS.get("break"), None);
+/// # }
+/// ```
+macro_rules! static_phf_map {
+    ($(#[$attr:meta])* $vis:vis static $name:ident : $t:ty = { $($key:expr => $value:expr),* $(,)* }) => {
+        $(#[$attr])*
+        $vis static $name: $t = $crate::__phf::phf_map! {
+            $($key => $value),*
+        };
+    };
+}
+
+#[cfg(feature = "phf")]
+#[macro_export]
+/// Declare a static **phf::Set** (from the `phf` crate) from a list of
+/// elements.
+///
+/// The elements must be literals, as required by `phf`.
+///
+/// Requires crate feature `"phf"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// extern crate phf;
+///
+/// static_phf_set!(pub static RESERVED: phf::Set<&'static str> = {
+///     "fn", "let", "match",
+/// });
+///
+/// # fn main() {
+/// assert!(RESERVED.contains("let"));
+/// assert!(!RESERVED.contains("foo"));
+/// # }
+/// ```
+macro_rules! static_phf_set {
+    ($(#[$attr:meta])* $vis:vis static $name:ident : $t:ty = { $($key:expr),* $(,)* }) => {
+        $(#[$attr])*
+        $vis static $name: $t = $crate::__phf::phf_set! {
+            $($key),*
+        };
+    };
+}
+
+#[cfg(feature = "wasm")]
+#[doc(hidden)]
+pub extern crate js_sys as __js_sys;
+
+#[cfg(feature = "wasm")]
+#[macro_export(local_inner_macros)]
+/// Create a JavaScript **Map** (`js_sys::Map`) from a list of key-value
+/// pairs.
+///
+/// Keys and values are converted with `JsValue::from`.
+///
+/// Requires crate feature `"wasm"`.
+///
+/// ## Example
+///
+/// ```no_run
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let options = jsmap!{
+///     "method" => "POST",
+///     "retries" => 3,
+/// };
+/// assert_eq!(options.size(), 2);
+/// # }
+/// ```
+macro_rules! jsmap {
+    ($($key:expr => $value:expr,)+) => (jsmap!($($key => $value),+));
+
+    ( $($key:expr => $value:expr),* ) => {
+        {
+            let _map = $crate::__js_sys::Map::new();
+            $(
+                let _ = _map.set(
+                    &$crate::__js_sys::wasm_bindgen::JsValue::from($key),
+                    &$crate::__js_sys::wasm_bindgen::JsValue::from($value));
+            )*
+            _map
+        }
+    };
+}
+
+#[cfg(feature = "arrayvec")]
+#[macro_export(local_inner_macros)]
+/// Create an **ArraySet** with capacity `cap` from a list of elements.
+///
+/// The capacity must be a constant expression. Listing more elements than
+/// the capacity is a compile error.
+///
+/// Requires crate feature `"arrayvec"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let set = arrayset!(cap = 4; "c", "a", "b");
+/// assert!(set.contains("a"));
+/// assert!(!set.contains("d"));
+/// assert_eq!(set.as_slice(), ["a", "b", "c"]);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+/// let set = arrayset!(cap = 2; 1, 2, 3);
+/// # }
+/// ```
+macro_rules! arrayset {
+    (@count $($rest:expr),*) => (__count!($($rest)*));
+
+    (cap = $cap:expr; $($key:expr,)+) => (arrayset!(cap = $cap; $($key),+));
+
+    (cap = $cap:expr; $($key:expr),*) => {
+        {
+            const _: () = ::std::assert!(arrayset!(@count $($key),*) <= $cap,
+                                         "arrayset!: more elements than the capacity");
+            let mut _set = $crate::ArraySet::<_, { $cap }>::new();
+            $(
+                let _ = _set.insert($key);
+            )*
+            _set
+        }
+    };
+}
+
+#[cfg(feature = "proptest")]
+#[doc(hidden)]
+pub extern crate proptest as __proptest;
+
+#[cfg(feature = "proptest")]
+#[macro_export(local_inner_macros)]
+/// Create a proptest **Strategy** that generates HashMaps.
+///
+/// The syntax is `hashmap_strategy!(keys, values, size)`, where `keys` and
+/// `values` are strategies and `size` is a size range like `0..16`. Keys
+/// are drawn until the map has a size in the range, so the key strategy must
+/// be able to produce enough distinct keys.
+///
+/// Requires crate feature `"proptest"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// extern crate proptest;
+/// # fn main() {
+///
+/// use proptest::prelude::*;
+/// use proptest::test_runner::TestRunner;
+///
+/// let strategy = hashmap_strategy!("[a-z]{1,8}", 0..100u32, 1..10);
+/// TestRunner::default().run(&strategy, |map| {
+///     prop_assert!(!map.is_empty() && map.len() < 10);
+///     prop_assert!(map.values().all(|&v| v < 100));
+///     Ok(())
+/// }).unwrap();
+/// # }
+/// ```
+macro_rules! hashmap_strategy {
+    ($keys:expr, $values:expr, $size:expr $(,)*) => {
+        $crate::__proptest::collection::hash_map($keys, $values, $size)
+    };
+}
+
+#[cfg(feature = "proptest")]
+#[macro_export(local_inner_macros)]
+/// Create a proptest **Strategy** that generates BTreeMaps.
+///
+/// The arguments are the same as for
+/// [`hashmap_strategy!`](macro.hashmap_strategy.html).
+///
+/// Requires crate feature `"proptest"`.
+macro_rules! btreemap_strategy {
+    ($keys:expr, $values:expr, $size:expr $(,)*) => {
+        $crate::__proptest::collection::btree_map($keys, $values, $size)
+    };
+}
+
+#[cfg(feature = "proptest")]
+#[macro_export(local_inner_macros)]
+/// Create a proptest **Strategy** that generates HashSets.
+///
+/// The syntax is `hashset_strategy!(elems, size)`, where `elems` is a
+/// strategy and `size` is a size range like `0..16`.
+///
+/// Requires crate feature `"proptest"`.
+macro_rules! hashset_strategy {
+    ($elems:expr, $size:expr $(,)*) => {
+        $crate::__proptest::collection::hash_set($elems, $size)
+    };
+}
+
+#[cfg(feature = "proptest")]
+#[macro_export(local_inner_macros)]
+/// Create a proptest **Strategy** that generates BTreeSets.
+///
+/// The arguments are the same as for
+/// [`hashset_strategy!`](macro.hashset_strategy.html).
+///
+/// Requires crate feature `"proptest"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// extern crate proptest;
+/// # fn main() {
+///
+/// use proptest::test_runner::TestRunner;
+///
+/// let strategy = btreeset_strategy!(0..1000i32, 5);
+/// TestRunner::default().run(&strategy, |set| {
+///     assert_eq!(set.len(), 5);
+///     Ok(())
+/// }).unwrap();
+/// # }
+/// ```
+macro_rules! btreeset_strategy {
+    ($elems:expr, $size:expr $(,)*) => {
+        $crate::__proptest::collection::btree_set($elems, $size)
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Create a **LiteralMap** from a list of key-value pairs.
+///
+/// A later entry with the same key replaces the earlier one, just like
+/// with `hashmap!`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// use std::collections::HashMap;
+///
+/// let map = literalmap!{
+///     "a" => 1,
+///     "b" => 2,
+/// };
+/// assert_eq!(map.get("a"), Some(&1));
+/// assert!(!map.contains_key("c"));
+///
+/// let map: HashMap<_, _> = map.into_hashmap();
+/// assert_eq!(map["b"], 2);
+/// # }
+/// ```
+macro_rules! literalmap {
+    (@count $($rest:expr),*) => (__count!($($rest)*));
+
+    ($($key:expr => $value:expr,)+) => { literalmap!($($key => $value),+) };
+    ($($key:expr => $value:expr),*) => {
+        {
+            let _cap = literalmap!(@count $($key),*);
+            let mut _map = $crate::LiteralMap::with_capacity(_cap);
+            $(
+                let _ = _map.insert($key, $value);
+            )*
+            _map
+        }
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Create a **DefaultHashMap** from an optional default factory and a list
+/// of key-value pairs.
+///
+/// Without a factory, missing keys default to `Default::default()`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let mut groups = defaultmap!(Vec::new;
+///     "a" => vec![1],
+/// );
+/// groups.get_or_insert("a").push(2);
+/// groups.get_or_insert("b").push(3);
+/// assert_eq!(groups["a"], [1, 2]);
+/// assert_eq!(groups["b"], [3]);
+///
+/// let counts = defaultmap!{"x" => 1};
+/// assert_eq!(*counts.get_or_default("x"), 1);
+/// assert_eq!(*counts.get_or_default("y"), 0);
+/// assert_eq!(counts["y"], 0);
+/// assert_eq!(counts.get("y"), None);
+/// # }
+/// ```
+macro_rules! defaultmap {
+    (@count $($rest:expr),*) => (__count!($($rest)*));
+
+    ($factory:expr; $($key:expr => $value:expr,)+) => { defaultmap!($factory; $($key => $value),+) };
+    ($factory:expr; $($key:expr => $value:expr),*) => {
+        {
+            let _cap = defaultmap!(@count $($key),*);
+            let mut _map = $crate::DefaultHashMap::with_capacity_and_factory(_cap, $factory);
+            $(
+                let _ = _map.insert($key, $value);
+            )*
+            _map
+        }
+    };
+    ($($key:expr => $value:expr,)+) => { defaultmap!($($key => $value),+) };
+    ($($key:expr => $value:expr),*) => {
+        {
+            let mut _map = $crate::DefaultHashMap::new();
+            $(
+                let _ = _map.insert($key, $value);
+            )*
+            _map
+        }
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Create a **Counter** from a list of elements, counting repeated elements.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let counts = counter!{"a", "b", "a"};
+/// assert_eq!(counts.count("a"), 2);
+/// assert_eq!(counts.count("b"), 1);
+/// assert_eq!(counts.count("c"), 0);
+/// assert_eq!(counts.most_common()[0], (&"a", 2));
+/// # }
+/// ```
+macro_rules! counter {
+    ($($key:expr,)+) => (counter!($($key),+));
+
+    ( $($key:expr),* ) => {
+        {
+            let mut _counter = $crate::Counter::new();
+            $(
+                _counter.add($key);
+            )*
+            _counter
+        }
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Create a **ChainMap** from a list of maps, highest priority first.
+///
+/// The maps are borrowed, not moved.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let defaults = hashmap!{"host" => "localhost", "port" => "80"};
+/// let overrides = hashmap!{"port" => "8080"};
+///
+/// let config = chainmap![overrides, defaults];
+/// assert_eq!(config.get("port"), Some(&"8080"));
+/// assert_eq!(config.get("host"), Some(&"localhost"));
+/// assert_eq!(config.find("host"), Some((1, &"localhost")));
+/// # }
+/// ```
+macro_rules! chainmap {
+    ($($map:expr,)+) => (chainmap!($($map),+));
+
+    ( $($map:expr),* ) => {
+        {
+            let mut _chain = $crate::ChainMap::new();
+            $(
+                _chain.push_layer(&$map);
+            )*
+            _chain
+        }
+    };
+}
+
+#[cfg(feature = "lazy-map")]
+#[macro_export(local_inner_macros)]
+/// Create a **LazyMap** from a list of key-closure pairs.
+///
+/// Each closure is called at most once, the first time its key is looked
+/// up.
+///
+/// Requires crate feature `"lazy-map"`, which needs Rust 1.80 or later for
+/// `std::cell::LazyCell`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// use std::cell::Cell;
+///
+/// let calls = Cell::new(0);
+/// let map = lazymap!{
+///     "cheap" => || 1,
+///     "expensive" => || { calls.set(calls.get() + 1); 2 },
+/// };
+/// assert_eq!(map.get("cheap"), Some(&1));
+/// assert_eq!(calls.get(), 0);
+/// assert_eq!(map.get("expensive"), Some(&2));
+/// assert_eq!(map.get("expensive"), Some(&2));
+/// assert_eq!(calls.get(), 1);
+/// # }
+/// ```
+macro_rules! lazymap {
+    (@count $($rest:expr),*) => (__count!($($rest)*));
+
+    ($($key:expr => $init:expr,)+) => { lazymap!($($key => $init),+) };
+    ($($key:expr => $init:expr),*) => {
+        {
+            let _cap = lazymap!(@count $($key),*);
+            let mut _map = $crate::LazyMap::with_capacity(_cap);
+            $(
+                _map.insert($key, $init);
+            )*
+            _map
+        }
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Create a **CaseInsensitiveMap** from a list of key-value pairs.
+///
+/// Keys are converted with `Into<String>`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let headers = cimap!{
+///     "Content-Type" => "text/html",
+///     "Content-Length" => "0",
+/// };
+/// assert_eq!(headers["content-type"], "text/html");
+/// assert_eq!(headers.get("CONTENT-LENGTH"), Some(&"0"));
+/// # }
+/// ```
+macro_rules! cimap {
+    (@count $($rest:expr),*) => (__count!($($rest)*));
+
+    ($($key:expr => $value:expr,)+) => { cimap!($($key => $value),+) };
+    ($($key:expr => $value:expr),*) => {
+        {
+            let _cap = cimap!(@count $($key),*);
+            let mut _map = $crate::CaseInsensitiveMap::with_capacity(_cap);
+            $(
+                let _ = _map.insert($key, $value);
+            )*
+            _map
+        }
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Create an **InternedMap** from a list of key-value pairs.
+///
+/// The keys must be string slices.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let map = internedmap!{
+///     "en" => "hello",
+///     "fr" => "bonjour",
+/// };
+/// assert_eq!(map.get("fr"), Some(&"bonjour"));
+///
+/// let en = map.interner().get("en").unwrap();
+/// assert_eq!(map.get_by_symbol(en), Some(&"hello"));
+/// # }
+/// ```
+macro_rules! internedmap {
+    (@count $($rest:expr),*) => (__count!($($rest)*));
+
+    ($($key:expr => $value:expr,)+) => { internedmap!($($key => $value),+) };
+    ($($key:expr => $value:expr),*) => {
+        {
+            let _cap = internedmap!(@count $($key),*);
+            let mut _map = $crate::InternedMap::with_capacity(_cap);
+            $(
+                let _ = _map.insert($key, $value);
+            )*
+            _map
+        }
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Create a **RangeSet** from a list of ranges.
+///
+/// Overlapping and adjacent ranges are merged. The values must be of a
+/// primitive integer type, or another type that implements
+/// [`Discrete`](range_set/trait.Discrete.html).
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let set = rangeset![0..10, 20..=25, 10..12];
+/// assert!(set.contains(&11));
+/// assert!(set.contains(&25));
+/// assert!(!set.contains(&12));
+/// assert_eq!(set.iter().collect::<Vec<_>>(), [0..=11, 20..=25]);
+/// # }
+/// ```
+macro_rules! rangeset {
+    ($($range:expr,)+) => (rangeset!($($range),+));
+
+    ( $($range:expr),* ) => {
+        {
+            let mut _set = $crate::RangeSet::new();
+            $(
+                _set.insert($range);
+            )*
+            _set
+        }
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Create a **TrieMap** from a list of key-value pairs.
+///
+/// The keys must be string slices.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let routes = triemap!{
+///     "/" => "index",
+///     "/api" => "api",
+///     "/api/users" => "users",
+/// };
+/// assert_eq!(routes.get("/api"), Some(&"api"));
+/// assert_eq!(routes.longest_prefix_match("/api/users/7"), Some(("/api/users", &"users")));
+/// assert_eq!(routes.longest_prefix_match("/about"), Some(("/", &"index")));
+///
+/// let keys: Vec<_> = routes.iter_prefix("/api").map(|(k, _)| k).collect();
+/// assert_eq!(keys, ["/api", "/api/users"]);
+/// # }
+/// ```
+macro_rules! triemap {
+    ($($key:expr => $value:expr,)+) => (triemap!($($key => $value),+));
+
+    ( $($key:expr => $value:expr),* ) => {
+        {
+            let mut _map = $crate::TrieMap::new();
+            $(
+                let _ = _map.insert($key, $value);
+            )*
+            _map
+        }
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Create a **TypeMap** from a list of type-value pairs.
+///
+/// Each value is stored under the given type, which must be the type of the
+/// value.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// struct Config { verbose: bool }
+/// struct Name(&'static str);
+///
+/// let state = typemap!{
+///     Config => Config { verbose: true },
+///     Name => Name("maplit"),
+/// };
+/// assert!(state.get::<Config>().unwrap().verbose);
+/// assert_eq!(state.get::<Name>().unwrap().0, "maplit");
+/// assert!(state.get::<String>().is_none());
+/// # }
+/// ```
+macro_rules! typemap {
+    ($($key:ty => $value:expr,)+) => (typemap!($($key => $value),+));
+
+    ( $($key:ty => $value:expr),* ) => {
+        {
+            let mut _map = $crate::TypeMap::new();
+            $(
+                let _ = _map.insert::<$key>($value);
+            )*
+            _map
+        }
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Create a **SortedVecMap** from a list of key-value pairs.
+///
+/// The entries are collected and sorted once. A later entry with the same
+/// key replaces the earlier one, just like with `btreemap!`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let map = sortedvecmap!{
+///     "b" => 2,
+///     "a" => 1,
+///     "c" => 3,
+/// };
+/// assert_eq!(map.get("a"), Some(&1));
+/// let keys: Vec<_> = map.iter().map(|(k, _)| *k).collect();
+/// assert_eq!(keys, ["a", "b", "c"]);
+/// # }
+/// ```
+macro_rules! sortedvecmap {
+    ($($key:expr => $value:expr,)+) => (sortedvecmap!($($key => $value),+));
+
+    ( $($key:expr => $value:expr),* ) => {
+        $crate::SortedVecMap::from_vec(<[_]>::into_vec(::std::boxed::Box::new([
+            $(($key, $value)),*
+        ])))
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Create a **PathMap** of nested maps from a list of path-value pairs.
+///
+/// Paths are split on `.`, or on the separator given with `sep = ...;`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let config = pathmap!{
+///     "server.tls.cert" => "cert.pem",
+///     "server.port" => "8080",
+/// };
+/// assert_eq!(config.get_value("server.port"), Some(&"8080"));
+/// assert!(config.get_path("server.tls").unwrap().as_map().is_some());
+///
+/// let routes = pathmap!(sep = '/'; "api/users" => 1);
+/// assert_eq!(routes.get_value("api/users"), Some(&1));
+/// # }
+/// ```
+macro_rules! pathmap {
+    (sep = $sep:expr; $($key:expr => $value:expr,)+) => (pathmap!(sep = $sep; $($key => $value),+));
+    (sep = $sep:expr; $($key:expr => $value:expr),*) => {
+        {
+            let mut _map = $crate::PathMap::with_separator($sep);
+            $(
+                let _ = _map.set_path($key, $value);
+            )*
+            _map
+        }
+    };
+    ($($key:expr => $value:expr,)+) => (pathmap!($($key => $value),+));
+    ($($key:expr => $value:expr),*) => (pathmap!(sep = '.'; $($key => $value),*));
+}
+
+#[macro_export(local_inner_macros)]
+/// Create a sparse grid, a **HashMap** keyed by `(row, column)`.
+///
+/// The grid can be written as a list of rows, or as a list of entries where
+/// each entry is either a single cell `(row, col) => value` or a whole row
+/// `row r => [values...]` starting at column zero.
+///
+/// See the [`grid`](grid/index.html) module for helper functions.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let tiles = grid![
+///     ['#', '#', '#'],
+///     ['#', '.', '#'],
+/// ];
+/// assert_eq!(tiles.len(), 6);
+/// assert_eq!(tiles[&(1, 1)], '.');
+///
+/// let sparse = grid!{
+///     (0, 0) => '#',
+///     row 3 => ['.', '.', '#'],
+/// };
+/// assert_eq!(sparse.len(), 4);
+/// assert_eq!(sparse[&(3, 2)], '#');
+/// # }
+/// ```
+macro_rules! grid {
+    (@row $grid:ident; $row:expr; $values:expr) => {
+        for (_col, _value) in <[_]>::into_vec(::std::boxed::Box::new($values))
+            .into_iter().enumerate()
+        {
+            let _ = $grid.insert(($row, _col), _value);
+        }
+    };
+
+    ($([$($value:expr),* $(,)*]),+ $(,)*) => {
+        {
+            let mut _grid = ::std::collections::HashMap::new();
+            let mut _row = 0;
+            $(
+                grid!(@row _grid; _row; [$($value),*]);
+                _row += 1;
+            )+
+            let _ = _row;
+            _grid
+        }
+    };
+    ($(($row:expr, $col:expr) => $value:expr),* $(,)*) => {
+        {
+            let mut _grid = ::std::collections::HashMap::new();
+            $(
+                let _ = _grid.insert(($row, $col), $value);
+            )*
+            _grid
+        }
+    };
+    // Each entry is either a cell or a row, told apart by its first token.
+    ($($(($row:expr, $col:expr))? $(row $whole_row:expr)? => $value:expr),+ $(,)*) => {
+        {
+            let mut _grid = ::std::collections::HashMap::new();
+            $(
+                $(let _ = _grid.insert(($row, $col), $value);)?
+                $(grid!(@row _grid; $whole_row; $value);)?
+            )+
+            _grid
+        }
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Create a **HashMap** and its reverse **HashMap** from a list of key-value
+/// pairs, returning `(forward, reverse)`.
+///
+/// Keys and values must implement `Clone`, `Hash` and `Eq`.
+///
+/// **Panics** if a key or a value is repeated, since the maps could not be
+/// each other's inverse.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let (color_id, id_color) = bidirectional!{
+///     "red" => 1,
+///     "green" => 2,
+/// };
+/// assert_eq!(color_id["green"], 2);
+/// assert_eq!(id_color[&1], "red");
+/// # }
+/// ```
+///
+/// ```should_panic
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+/// let _ = bidirectional!{"red" => 1, "crimson" => 1};
+/// # }
+/// ```
+macro_rules! bidirectional {
+    (@count $($rest:expr),*) => (__count!($($rest)*));
+
+    ($($key:expr => $value:expr,)+) => { bidirectional!($($key => $value),+) };
+    ($($key:expr => $value:expr),*) => {
+        {
+            let _cap = bidirectional!(@count $($key),*);
+            let mut _forward = ::std::collections::HashMap::with_capacity(_cap);
+            let mut _reverse = ::std::collections::HashMap::with_capacity(_cap);
+            $(
+                let _key = $key;
+                let _value = $value;
+                if _reverse.insert(::std::clone::Clone::clone(&_value),
+                                   ::std::clone::Clone::clone(&_key)).is_some() {
+                    ::std::panic!("bidirectional!: duplicate value");
+                }
+                if _forward.insert(_key, _value).is_some() {
+                    ::std::panic!("bidirectional!: duplicate key");
+                }
+            )*
+            (_forward, _reverse)
+        }
+    };
+}
+
+#[cfg(feature = "once_cell")]
+extern crate once_cell as __once_cell;
+
+#[cfg(any(feature = "static-maps", feature = "once_cell"))]
+#[macro_export(local_inner_macros)]
+/// Declare a lazily initialized static **HashMap** from a list of key-value
+/// pairs.
+///
+/// The static has type [`Lazy<T>`](struct.Lazy.html) where `T` is the
+/// declared type, and it is built with [`hashmap!`](macro.hashmap.html) on
+/// first access.
+///
+/// Requires crate feature `"static-maps"`, which needs Rust 1.80, or crate
+/// feature `"once_cell"`, which works with older compilers too.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// use std::collections::HashMap;
+///
+/// static_hashmap!(pub static COLORS: HashMap<&str, u32> = {
+///     "red" => 0xff0000,
+///     "green" => 0x00ff00,
+/// });
+///
+/// # fn main() {
+/// assert_eq!(COLORS["red"], 0xff0000);
+/// # }
+/// ```
+macro_rules! static_hashmap {
+    ($(#[$attr:meta])* $vis:vis static $name:ident : $t:ty = { $($key:expr => $value:expr),* $(,)* }) => {
+        $(#[$attr])*
+        $vis static $name: $crate::Lazy<$t> =
+            $crate::Lazy::__new(|| hashmap!($($key => $value),*));
+    };
+}
+
+#[cfg(any(feature = "static-maps", feature = "once_cell"))]
+#[macro_export(local_inner_macros)]
+/// Declare a lazily initialized static **HashSet** from a list of elements.
+///
+/// The static has type [`Lazy<T>`](struct.Lazy.html) where `T` is the
+/// declared type. Requires crate feature `"static-maps"` or `"once_cell"`,
+/// like [`static_hashmap!`](macro.static_hashmap.html).
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// use std::collections::HashSet;
+///
+/// static_hashset!(static KEYWORDS: HashSet<&str> = { "fn", "let" });
+///
+/// # fn main() {
+/// assert!(KEYWORDS.contains("let"));
+/// # }
+/// ```
+macro_rules! static_hashset {
+    ($(#[$attr:meta])* $vis:vis static $name:ident : $t:ty = { $($key:expr),* $(,)* }) => {
+        $(#[$attr])*
+        $vis static $name: $crate::Lazy<$t> =
+            $crate::Lazy::__new(|| hashset!($($key),*));
+    };
+}
+
+#[cfg(any(feature = "static-maps", feature = "once_cell"))]
+#[macro_export(local_inner_macros)]
+/// Declare a lazily initialized static **BTreeMap** from a list of key-value
+/// pairs.
+///
+/// The static has type [`Lazy<T>`](struct.Lazy.html) where `T` is the
+/// declared type. Requires crate feature `"static-maps"` or `"once_cell"`,
+/// like [`static_hashmap!`](macro.static_hashmap.html).
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// use std::collections::BTreeMap;
+///
+/// static_btreemap!(static LIMITS: BTreeMap<&str, usize> = {
+///     "small" => 10,
+///     "large" => 1000,
+/// });
+///
+/// # fn main() {
+/// assert_eq!(LIMITS.keys().next(), Some(&"large"));
+/// # }
+/// ```
+macro_rules! static_btreemap {
+    ($(#[$attr:meta])* $vis:vis static $name:ident : $t:ty = { $($key:expr => $value:expr),* $(,)* }) => {
+        $(#[$attr])*
+        $vis static $name: $crate::Lazy<$t> =
+            $crate::Lazy::__new(|| btreemap!($($key => $value),*));
+    };
+}
+
+#[cfg(any(feature = "static-maps", feature = "once_cell"))]
+#[macro_export(local_inner_macros)]
+/// Declare a lazily initialized static **BTreeSet** from a list of elements.
+///
+/// The static has type [`Lazy<T>`](struct.Lazy.html) where `T` is the
+/// declared type. Requires crate feature `"static-maps"` or `"once_cell"`,
+/// like [`static_hashmap!`](macro.static_hashmap.html).
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// use std::collections::BTreeSet;
+///
+/// static_btreeset!(static PRIMES: BTreeSet<u32> = { 2, 3, 5, 7 });
+///
+/// # fn main() {
+/// assert!(PRIMES.contains(&5));
+/// # }
+/// ```
+macro_rules! static_btreeset {
+    ($(#[$attr:meta])* $vis:vis static $name:ident : $t:ty = { $($key:expr),* $(,)* }) => {
+        $(#[$attr])*
+        $vis static $name: $crate::Lazy<$t> =
+            $crate::Lazy::__new(|| btreeset!($($key),*));
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Create an **Arc&lt;HashMap&gt;** from a list of key-value pairs.
+///
+/// This is `Arc::new(hashmap!{...})`, useful for read-only tables shared
+/// between threads.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let map = arc_hashmap!{
+///     "a" => 1,
+///     "b" => 2,
+/// };
+/// let shared = map.clone();
+/// let handle = std::thread::spawn(move || shared["a"]);
+/// assert_eq!(handle.join().unwrap(), 1);
+/// assert_eq!(map["b"], 2);
+/// # }
+/// ```
+macro_rules! arc_hashmap {
+    ($($key:expr => $value:expr),* $(,)*) => {
+        ::std::sync::Arc::new(hashmap!($($key => $value),*))
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Create an **Arc&lt;HashSet&gt;** from a list of elements.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let set = arc_hashset!{"a", "b"};
+/// assert!(set.contains("a"));
+/// # }
+/// ```
+macro_rules! arc_hashset {
+    ($($key:expr),* $(,)*) => {
+        ::std::sync::Arc::new(hashset!($($key),*))
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Create an **Arc&lt;BTreeMap&gt;** from a list of key-value pairs.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let map = arc_btreemap!{"a" => 1, "b" => 2};
+/// assert_eq!(map["a"], 1);
+/// # }
+/// ```
+macro_rules! arc_btreemap {
+    ($($key:expr => $value:expr),* $(,)*) => {
+        ::std::sync::Arc::new(btreemap!($($key => $value),*))
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Create an **Arc&lt;BTreeSet&gt;** from a list of elements.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let set = arc_btreeset!{"a", "b"};
+/// assert!(set.contains("b"));
+/// # }
+/// ```
+macro_rules! arc_btreeset {
+    ($($key:expr),* $(,)*) => {
+        ::std::sync::Arc::new(btreeset!($($key),*))
+    };
+}
+
+#[cfg(any(feature = "static-maps", feature = "once_cell"))]
+#[macro_export(local_inner_macros)]
+/// Declare a global, mutable map protected by a `Mutex` or `RwLock` and
+/// seeded from a list of key-value pairs.
+///
+/// The declared type is `Mutex<M>` or `RwLock<M>`, where `M` is any map type
+/// that implements `FromIterator<(K, V)>`. The static itself is a lazy static
+/// (see [`static_hashmap!`](macro.static_hashmap.html)) that is initialized
+/// on first access, and needs the same crate features.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// use std::collections::{BTreeMap, HashMap};
+///
+/// global_map!(pub static REGISTRY: RwLock<HashMap<&str, u32>> = {
+///     "builtin" => 1,
+/// });
+/// global_map!(static COUNTS: Mutex<BTreeMap<String, usize>> = {});
+///
+/// # fn main() {
+/// REGISTRY.write().unwrap().insert("plugin", 2);
+/// assert_eq!(REGISTRY.read().unwrap()["plugin"], 2);
+///
+/// *COUNTS.lock().unwrap().entry("x".to_string()).or_insert(0) += 1;
+/// assert_eq!(COUNTS.lock().unwrap()["x"], 1);
+/// # }
+/// ```
+macro_rules! global_map {
+    ($(#[$attr:meta])* $vis:vis static $name:ident : Mutex<$t:ty> = { $($key:expr => $value:expr),* $(,)* }) => {
+        $(#[$attr])*
+        $vis static $name: $crate::Lazy<::std::sync::Mutex<$t>> =
+            $crate::Lazy::__new(|| ::std::sync::Mutex::new(global_map!(@build $t; $($key => $value),*)));
+    };
+    ($(#[$attr:meta])* $vis:vis static $name:ident : RwLock<$t:ty> = { $($key:expr => $value:expr),* $(,)* }) => {
+        $(#[$attr])*
+        $vis static $name: $crate::Lazy<::std::sync::RwLock<$t>> =
+            $crate::Lazy::__new(|| ::std::sync::RwLock::new(global_map!(@build $t; $($key => $value),*)));
+    };
+
+    (@build $t:ty; $($key:expr => $value:expr),*) => {
+        <$t as ::std::iter::FromIterator<_>>::from_iter(
+            <[_]>::into_vec(::std::boxed::Box::new([$(($key, $value)),*])))
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Create a **HashMap** with `PathBuf` keys from a list of key-value pairs.
+///
+/// Each key is converted with `PathBuf::from`, so string literals, `&Path`,
+/// `OsString` and `String` keys can be used directly. This is the same as
+/// `hashmap!{@keys path; ...}`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// use std::path::Path;
+///
+/// let overrides = pathkeyed!{
+///     "/etc/hosts" => "hosts.test",
+///     "/etc/resolv.conf" => "resolv.test",
+/// };
+/// assert_eq!(overrides[Path::new("/etc/hosts")], "hosts.test");
+/// # }
+/// ```
+macro_rules! pathkeyed {
+    ($($key:expr => $value:expr),* $(,)*) => {
+        hashmap!(@keys path; $($key => $value),*)
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Create a **HashMap** with `CString` keys and values from a list of
+/// key-value pairs, for tables that are passed over FFI.
+///
+/// Keys and values may be anything `CString::new` accepts, for example
+/// string slices, `String` or byte vectors.
+///
+/// Returns `Result<HashMap<CString, CString>, NulError>`, with an error for
+/// the first key or value that contains a nul byte.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// use std::ffi::CString;
+///
+/// let env = cstringmap!{
+///     "LANG" => "C",
+///     "HOME" => String::from("/root"),
+/// }.unwrap();
+/// assert_eq!(env[&CString::new("LANG").unwrap()].as_bytes(), b"C");
+///
+/// assert!(cstringmap!{"BAD" => "a\0b"}.is_err());
+/// # }
+/// ```
+macro_rules! cstringmap {
+    (@count $($rest:expr),*) => (__count!($($rest)*));
+
+    ($($key:expr => $value:expr,)+) => { cstringmap!($($key => $value),+) };
+    ($($key:expr => $value:expr),*) => {
+        {
+            let _cap = cstringmap!(@count $($key),*);
+            let mut _map: ::std::collections::HashMap<::std::ffi::CString, ::std::ffi::CString> =
+                ::std::collections::HashMap::with_capacity(_cap);
+            let mut _result: ::std::result::Result<(), ::std::ffi::NulError> =
+                ::std::result::Result::Ok(());
+            $(
+                if _result.is_ok() {
+                    _result = $crate::__insert_cstrings(&mut _map, $key, $value);
+                }
+            )*
+            _result.map(|()| _map)
+        }
+    };
+}
+
+/// Convert `key` and `value` to `CString` and insert them into `map`.
+///
+/// `cstringmap!` evaluates each entry in the caller, so that `?` and
+/// `return` in it apply there, and converts it here.
+#[doc(hidden)]
+// `NulError` has been in `std::ffi` since Rust 1.0, but clippy dates it by
+// its move to `alloc` in 1.64.
+#[allow(clippy::incompatible_msrv)]
+pub fn __insert_cstrings<K, V>(map: &mut ::std::collections::HashMap<::std::ffi::CString,
+                                                                      ::std::ffi::CString>,
+                               key: K, value: V)
+    -> Result<(), ::std::ffi::NulError>
+    where K: Into<Vec<u8>>, V: Into<Vec<u8>>
+{
+    let key = ::std::ffi::CString::new(key)?;
+    let value = ::std::ffi::CString::new(value)?;
+    let _ = map.insert(key, value);
+    Ok(())
+}
+
+#[macro_export(local_inner_macros)]
+/// Create a **HashMap** from a list of keys, with the same initial value for
+/// every key.
+///
+/// `keyset_map!{k1, k2 => Type}` uses `Type::default()` for each value.
+/// `keyset_map!{k1, k2; expr}` evaluates `expr` once per key. With neither,
+/// the value type is inferred and `Default::default()` is used.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let mut groups = keyset_map!{"even", "odd" => Vec<i32>};
+/// for i in 0..5 {
+///     groups.get_mut(if i % 2 == 0 { "even" } else { "odd" }).unwrap().push(i);
+/// }
+/// assert_eq!(groups["even"], [0, 2, 4]);
+///
+/// let buffers = keyset_map!{1, 2, 3; String::with_capacity(16)};
+/// assert!(buffers.values().all(|b| b.capacity() >= 16));
+/// # }
+/// ```
+macro_rules! keyset_map {
+    ($($key:expr),* $(,)* => $t:ty) => {
+        hashmap!($($key => <$t as ::std::default::Default>::default()),*)
+    };
+    ($($key:expr),* $(,)* ; $value:expr) => {
+        hashmap!($($key => $value),*)
+    };
+    ($($key:expr),* $(,)*) => {
+        hashmap!($($key => ::std::default::Default::default()),*)
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Create any map that implements [`MapInsert`](trait.MapInsert.html) and
+/// `Default` from a list of key-value pairs.
+///
+/// The syntax is `map_of!(Type; key => value, ...)`, where the type can
+/// leave its parameters to be inferred, as in `BTreeMap<_, _>`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// use maplit::SortedVecMap;
+///
+/// let map = map_of!(SortedVecMap<_, _>;
+///     "b" => 2,
+///     "a" => 1,
+/// );
+/// assert_eq!(map.as_slice(), [("a", 1), ("b", 2)]);
+/// # }
+/// ```
+macro_rules! map_of {
+    ($t:ty; $($key:expr => $value:expr),* $(,)*) => {
+        {
+            let mut _map: $t = ::std::default::Default::default();
+            $crate::MapInsert::reserve_entries(&mut _map, hashmap!(@count $($key),*));
+            $(
+                $crate::MapInsert::map_insert(&mut _map, $key, $value);
+            )*
+            _map
+        }
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Create any set that implements [`SetAdd`](trait.SetAdd.html) and
+/// `Default` from a list of elements.
+///
+/// The syntax is `set_of!(Type; elem, ...)`, where the type can leave its
+/// parameter to be inferred, as in `BTreeSet<_>`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// // a `Vec` is built as a sorted vector without duplicates
+/// let set = set_of!(Vec<_>; 3, 1, 2, 1);
+/// assert_eq!(set, [1, 2, 3]);
+/// # }
+/// ```
+macro_rules! set_of {
+    ($t:ty; $($elem:expr),* $(,)*) => {
+        {
+            let mut _set: $t = ::std::default::Default::default();
+            $crate::SetAdd::reserve_elems(&mut _set, hashmap!(@count $($elem),*));
+            $(
+                $crate::SetAdd::set_add(&mut _set, $elem);
+            )*
+            _set
+        }
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Insert a list of key-value pairs into an existing map.
+///
+/// The syntax is `insert_all!(map; key => value, ...)`. It works with any
+/// map that has an `insert(key, value)` method.
+///
+/// By default, a listed entry replaces the existing entry for its key. A
+/// policy can be given before the entries:
+///
+/// - `@keep;` keeps existing entries, using `entry(key).or_insert(value)`.
+/// - `@unique;` panics if a key is already in the map.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let mut map = hashmap!{"a" => 1};
+/// insert_all!(map; "a" => 10, "b" => 2);
+/// assert_eq!(map, hashmap!{"a" => 10, "b" => 2});
+///
+/// insert_all!(map; @keep; "b" => 20, "c" => 3);
+/// assert_eq!(map, hashmap!{"a" => 10, "b" => 2, "c" => 3});
+/// # }
+/// ```
+macro_rules! insert_all {
+    ($map:expr; @keep; $($key:expr => $value:expr),* $(,)*) => {
+        {
+            let _map = &mut $map;
+            $(
+                let _ = _map.entry($key).or_insert($value);
+            )*
+        }
+    };
+    ($map:expr; @unique; $($key:expr => $value:expr),* $(,)*) => {
+        {
+            let _map = &mut $map;
+            $(
+                if _map.insert($key, $value).is_some() {
+                    ::std::panic!("insert_all!: duplicate key `{}`", ::std::stringify!($key));
+                }
+            )*
+        }
+    };
+    ($map:expr; $($key:expr => $value:expr),* $(,)*) => {
+        {
+            let _map = &mut $map;
+            $(
+                let _ = _map.insert($key, $value);
+            )*
+        }
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Add a list of elements to an existing set.
+///
+/// The syntax is `add_all!(set; elem, ...)`. It works with any set that has
+/// an `insert(elem)` method.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let mut set = btreeset!{1};
+/// add_all!(set; 3, 2, 1);
+/// assert_eq!(set, btreeset!{1, 2, 3});
+/// # }
+/// ```
+macro_rules! add_all {
+    ($set:expr; $($elem:expr),* $(,)*) => {
+        {
+            let _set = &mut $set;
+            $(
+                let _ = _set.insert($elem);
+            )*
+        }
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Create a copy of a map with a list of key-value pairs inserted.
+///
+/// `with!(base; key => value, ...)` clones `base`, which may also be a
+/// reference to a map, and `with!(move base; ...)` takes it by value
+/// instead. The entries are inserted as by [`insert_all!`](macro.insert_all.html),
+/// including its `@keep;` and `@unique;` policies.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let defaults = hashmap!{"color" => "auto", "pager" => "less"};
+/// let config = with!(defaults; "pager" => "more");
+/// assert_eq!(config["pager"], "more");
+/// assert_eq!(defaults["pager"], "less");
+///
+/// let config = with!(move config; @keep; "color" => "never");
+/// assert_eq!(config["color"], "auto");
+/// # }
+/// ```
+macro_rules! with {
+    (move $base:expr; $($rest:tt)*) => {
+        {
+            let mut _map = $base;
+            insert_all!(_map; $($rest)*);
+            _map
+        }
+    };
+    ($base:expr; $($rest:tt)*) => {
+        {
+            let mut _map = $base.clone();
+            insert_all!(_map; $($rest)*);
+            _map
+        }
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Update the entries for several keys of a map with the entry API.
+///
+/// The syntax is `update!(map; key => f, or default; ...)`, with entries
+/// separated by semicolons. For each entry, `f` is called with a mutable
+/// reference to the value if the key is present; otherwise `default` is
+/// inserted. Without `, or default`, absent keys are left alone.
+///
+/// This is `map.entry(key).and_modify(f).or_insert(default)` for each
+/// entry, so it works with `HashMap` and `BTreeMap`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let mut stats = hashmap!{"hits" => 4, "misses" => 1};
+/// update!(stats;
+///     "hits" => |v| *v += 1, or 1;
+///     "errors" => |v| *v += 1, or 1;
+///     "misses" => |v| *v = 0;
+///     "skipped" => |v| *v = 0;
+/// );
+/// assert_eq!(stats, hashmap!{"hits" => 5, "misses" => 0, "errors" => 1});
+/// # }
+/// ```
+macro_rules! update {
+    (@entry $map:ident; $key:expr => $f:expr, or $default:expr) => {
+        let _ = $map.entry($key).and_modify($f).or_insert($default);
+    };
+    (@entry $map:ident; $key:expr => $f:expr) => {
+        let _ = $map.entry($key).and_modify($f);
+    };
+    ($map:expr; $($key:expr => $f:expr $(, or $default:expr)?);* $(;)*) => {
+        {
+            let _map = &mut $map;
+            $(
+                update!(@entry _map; $key => $f $(, or $default)?);
+            )*
+        }
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Look up a value in nested maps by a list of keys.
+///
+/// `get_path!(map, k1, k2, k3)` is `map.get(k1)` followed by `.get(k2)` and
+/// `.get(k3)` on the nested maps, and returns `Option<&V>`. It works with
+/// any maps that have a `get` method, so `HashMap` and `BTreeMap` can be
+/// mixed. See also [`get_path_mut!`](macro.get_path_mut.html).
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let config = hashmap!{
+///     "server" => hashmap!{
+///         "tls" => hashmap!{"cert" => "server.pem"},
+///     },
+/// };
+/// assert_eq!(get_path!(config, "server", "tls", "cert"), Some(&"server.pem"));
+/// assert_eq!(get_path!(config, "server", "http", "cert"), None);
+/// # }
+/// ```
+macro_rules! get_path {
+    (@chain $acc:expr;) => ($acc);
+    (@chain $acc:expr; $key:expr $(, $rest:expr)*) => {
+        get_path!(@chain $acc.and_then(|_m| _m.get($key)); $($rest),*)
+    };
+    ($map:expr, $first:expr $(, $rest:expr)* $(,)*) => {
+        get_path!(@chain $map.get($first); $($rest),*)
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Look up a value in nested maps by a list of keys, for modification.
+///
+/// This is [`get_path!`](macro.get_path.html) using `get_mut`, and returns
+/// `Option<&mut V>`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let mut limits = btreemap!{"user" => btreemap!{"files" => 10}};
+/// if let Some(files) = get_path_mut!(limits, "user", "files") {
+///     *files *= 2;
+/// }
+/// assert_eq!(limits["user"]["files"], 20);
+/// # }
+/// ```
+macro_rules! get_path_mut {
+    (@chain $acc:expr;) => ($acc);
+    (@chain $acc:expr; $key:expr $(, $rest:expr)*) => {
+        get_path_mut!(@chain $acc.and_then(|_m| _m.get_mut($key)); $($rest),*)
+    };
+    ($map:expr, $first:expr $(, $rest:expr)* $(,)*) => {
+        get_path_mut!(@chain $map.get_mut($first); $($rest),*)
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Remove a list of keys from a map, returning the removed values.
+///
+/// `remove_keys!(map; k1, k2, ...)` calls `map.remove(k)` for each key, in
+/// order, and returns a `Vec<Option<V>>` of the results. The keys are passed
+/// as they are, so they are borrowed forms like `"a"` or `&1`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let mut sessions = hashmap!{"alice" => 1, "bob" => 2};
+/// let removed = remove_keys!(sessions; "alice", "carol");
+/// assert_eq!(removed, [Some(1), None]);
+/// assert_eq!(sessions.len(), 1);
+/// # }
+/// ```
+macro_rules! remove_keys {
+    ($map:expr; $($key:expr),* $(,)*) => {
+        {
+            let _map = &mut $map;
+            <[_]>::into_vec(::std::boxed::Box::new([$(_map.remove($key)),*]))
+        }
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Remove a list of elements from a set, returning how many were present.
+///
+/// The elements are passed to `set.remove` as they are, like the keys of
+/// [`remove_keys!`](macro.remove_keys.html).
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let mut set = btreeset!{1, 2, 3};
+/// assert_eq!(remove_elems!(set; &1, &3, &5), 2);
+/// assert_eq!(set, btreeset!{2});
+/// # }
+/// ```
+macro_rules! remove_elems {
+    ($set:expr; $($elem:expr),* $(,)*) => {
+        {
+            let _set = &mut $set;
+            let mut _count = 0usize;
+            $(
+                if _set.remove($elem) {
+                    _count += 1;
+                }
+            )*
+            _count
+        }
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Merge maps and inline entries into a new map.
+///
+/// The arguments are a comma separated list of map expressions and entry
+/// blocks `{ key => value, ... }`. Later arguments take precedence, so a key
+/// gets the value it has in the last argument that contains it.
+///
+/// The map arguments are taken by value (anything that can be iterated as
+/// key-value pairs works after the first), and the result has the type of
+/// the first argument. A leading entry block is built as a `HashMap`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let defaults = hashmap!{"color" => "auto", "pager" => "less"};
+/// let user = hashmap!{"pager" => "more"};
+/// let config = map_merge!(defaults.clone(), user, { "color" => "never" });
+/// assert_eq!(config, hashmap!{"color" => "never", "pager" => "more"});
+/// # }
+/// ```
+macro_rules! map_merge {
+    (@merge $map:ident;) => {};
+    (@merge $map:ident; , $($rest:tt)*) => {
+        map_merge!(@merge $map; $($rest)*)
+    };
+    (@merge $map:ident; { $($key:expr => $value:expr),* $(,)* } $($rest:tt)*) => {
+        insert_all!($map; $($key => $value),*);
+        map_merge!(@merge $map; $($rest)*)
+    };
+    (@merge $map:ident; $m:expr) => {
+        ::std::iter::Extend::extend(&mut $map, $m);
+    };
+    (@merge $map:ident; $m:expr, $($rest:tt)*) => {
+        ::std::iter::Extend::extend(&mut $map, $m);
+        map_merge!(@merge $map; $($rest)*)
+    };
+
+    ({ $($key:expr => $value:expr),* $(,)* } $($rest:tt)*) => {
+        {
+            let mut _map = hashmap!($($key => $value),*);
+            map_merge!(@merge _map; $($rest)*);
+            _map
+        }
+    };
+    ($first:expr) => ($first);
+    ($first:expr, $($rest:tt)*) => {
+        {
+            let mut _map = $first;
+            map_merge!(@merge _map; $($rest)*);
+            _map
+        }
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Merge maps into a new map, resolving conflicts with a function.
+///
+/// The syntax is `map_merge_with!(f; map1, map2, ...)`. The maps are taken
+/// by value and the result has the type of the first map. When a key is
+/// already present, its value becomes `f(&key, old_value, new_value)`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let monday = hashmap!{"apples" => 3, "pears" => 1};
+/// let tuesday = hashmap!{"apples" => 2, "plums" => 5};
+/// let total = map_merge_with!(|_, a, b| a + b; monday, tuesday);
+/// assert_eq!(total, hashmap!{"apples" => 5, "pears" => 1, "plums" => 5});
+///
+/// let tags = btreemap!{"a" => vec![1]};
+/// let more = btreemap!{"a" => vec![2], "b" => vec![3]};
+/// let tags = map_merge_with!(|_, mut a, b| { a.extend(b); a }; tags, more);
+/// assert_eq!(tags["a"], [1, 2]);
+/// # }
+/// ```
+macro_rules! map_merge_with {
+    ($f:expr; $first:expr $(, $m:expr)* $(,)*) => {
+        {
+            let mut _map = $first;
+            #[allow(unused_mut)]
+            let mut _f = $crate::__merge_resolver(&_map, $f);
+            $(
+                for (_k, _v) in $m {
+                    let _v = match _map.remove(&_k) {
+                        ::std::option::Option::Some(_old) => _f(&_k, _old, _v),
+                        ::std::option::Option::None => _v,
+                    };
+                    let _ = _map.insert(_k, _v);
+                }
+            )*
+            _map
+        }
+    };
+}
+
+/// Return the resolver function of `map_merge_with!`, letting the compiler
+/// infer its argument types from the map.
+#[doc(hidden)]
+pub fn __merge_resolver<'a, M, K: 'a, V: 'a, F>(_map: &'a M, f: F) -> F
+    where &'a M: IntoIterator<Item=(&'a K, &'a V)>,
+          F: FnMut(&K, V, V) -> V
+{
+    f
+}
+
+#[macro_export(local_inner_macros)]
+/// Merge nested maps recursively.
+///
+/// `deep_merge!(map1, map2, ...)` takes the maps by value and merges them
+/// from left to right: inner maps under the same key are merged instead of
+/// replaced, while other values are replaced. See
+/// [`DeepMerge`](deep_merge/trait.DeepMerge.html) for the value types it
+/// supports.
+///
+/// `deep_merge!(depth = n; map1, map2, ...)` merges only the top `n` levels
+/// of maps; deeper maps are replaced as a whole. Depth 1 merges just the
+/// top level, like [`map_merge!`](macro.map_merge.html).
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let base = hashmap!{
+///     "server" => hashmap!{"port" => 80, "workers" => 4},
+/// };
+/// let local = hashmap!{
+///     "server" => hashmap!{"port" => 8080},
+/// };
+/// let config = deep_merge!(base.clone(), local.clone());
+/// assert_eq!(config["server"], hashmap!{"port" => 8080, "workers" => 4});
+///
+/// let config = deep_merge!(depth = 1; base, local);
+/// assert_eq!(config["server"], hashmap!{"port" => 8080});
+/// # }
+/// ```
+macro_rules! deep_merge {
+    (depth = $depth:expr; $first:expr $(, $m:expr)* $(,)*) => {
+        {
+            let mut _map = $first;
+            $(
+                $crate::DeepMerge::deep_merge_to_depth(&mut _map, $m, $depth);
+            )*
+            _map
+        }
+    };
+    ($first:expr $(, $m:expr)* $(,)*) => {
+        {
+            let mut _map = $first;
+            $(
+                $crate::DeepMerge::deep_merge(&mut _map, $m);
+            )*
+            _map
+        }
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Create a **HashMap** from values to keys by swapping the keys and values
+/// of a map, or of a list of key-value pairs.
+///
+/// - `invert!(map)` takes any map (anything that iterates as key-value
+///   pairs) by value, and panics if two keys have the same value.
+/// - `invert!(@result; map)` returns `Result<HashMap<V, K>, V>` instead,
+///   with an error for a value that appears twice.
+/// - `invert!(@last; map)` lets the key that comes last in iteration order
+///   win.
+/// - `invert!{key => value, ...}` inverts the listed entries, and panics if
+///   two keys have the same value.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let codes = hashmap!{"ok" => 200, "not found" => 404};
+/// let names = invert!(codes);
+/// assert_eq!(names[&404], "not found");
+///
+/// let aliases = btreemap!{"colour" => "color", "color" => "color"};
+/// assert_eq!(invert!(@result; aliases), Err("color"));
+///
+/// let ports = invert!{"http" => 80, "https" => 443};
+/// assert_eq!(ports[&443], "https");
+/// # }
+/// ```
+macro_rules! invert {
+    (@result; $map:expr) => ($crate::__invert($map));
+    (@last; $map:expr) => {
+        $crate::__id::<::std::collections::HashMap<_, _>>(
+            ::std::iter::IntoIterator::into_iter($map).map(|(_k, _v)| (_v, _k)).collect())
+    };
+    ($($key:expr => $value:expr),+ $(,)*) => {
+        invert!(<[_]>::into_vec(::std::boxed::Box::new([$(($key, $value)),+])))
+    };
+    ($map:expr) => {
+        match $crate::__invert($map) {
+            ::std::result::Result::Ok(_map) => _map,
+            ::std::result::Result::Err(_) => ::std::panic!("invert!: two keys have the same value"),
+        }
+    };
+}
+
+/// Invert `map`, failing on the first repeated value. Used by `invert!`.
+#[doc(hidden)]
+pub fn __invert<M, K, V>(map: M) -> Result<std::collections::HashMap<V, K>, V>
+    where M: IntoIterator<Item=(K, V)>, V: std::hash::Hash + Eq
+{
+    use std::collections::hash_map::{self, HashMap};
+
+    let iter = map.into_iter();
+    let mut inverse = HashMap::with_capacity(iter.size_hint().0);
+    for (key, value) in iter {
+        match inverse.entry(value) {
+            hash_map::Entry::Occupied(e) => return Err(e.remove_entry().0),
+            hash_map::Entry::Vacant(e) => {
+                let _ = e.insert(key);
+            }
+        }
+    }
+    Ok(inverse)
+}
+
+#[macro_export(local_inner_macros)]
+/// Create a copy of a map with only the listed keys.
+///
+/// `submap!(map; k1, k2, ...)` clones the entries for the listed keys that
+/// are present into a new map of the same type. The keys are borrowed forms,
+/// like `"a"` or `&1`, and the map needs a `get_key_value` method, like
+/// `HashMap` and `BTreeMap` have since Rust 1.40.
+///
+/// `submap!(map; @result; k1, k2, ...)` returns `Result<Map, Key>` instead,
+/// with an error for the first listed key that is missing. The keys after
+/// it are not evaluated.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let response = hashmap!{"id" => "7", "name" => "x", "secret" => "hunter2"};
+/// let public = submap!(response; "id", "name", "email");
+/// assert_eq!(public, hashmap!{"id" => "7", "name" => "x"});
+///
+/// assert_eq!(submap!(response; @result; "id", "email"), Err("email"));
+/// # }
+/// ```
+macro_rules! submap {
+    ($map:expr; @result; $($key:expr),* $(,)*) => {
+        {
+            let _map = &$map;
+            let mut _out = $crate::__empty_like(_map);
+            let mut _missing = ::std::option::Option::None;
+            $(
+                if _missing.is_none() {
+                    let _key = $key;
+                    match _map.get_key_value(_key) {
+                        ::std::option::Option::Some((_k, _v)) => {
+                            let _ = _out.insert(::std::clone::Clone::clone(_k), ::std::clone::Clone::clone(_v));
+                        }
+                        ::std::option::Option::None => _missing = ::std::option::Option::Some(_key),
+                    }
+                }
+            )*
+            match _missing {
+                ::std::option::Option::None => ::std::result::Result::Ok(_out),
+                ::std::option::Option::Some(_key) => ::std::result::Result::Err(_key),
+            }
+        }
+    };
+    ($map:expr; $($key:expr),* $(,)*) => {
+        {
+            let _map = &$map;
+            let mut _out = $crate::__empty_like(_map);
+            $(
+                if let ::std::option::Option::Some((_k, _v)) = _map.get_key_value($key) {
+                    let _ = _out.insert(::std::clone::Clone::clone(_k), ::std::clone::Clone::clone(_v));
+                }
+            )*
+            _out
+        }
+    };
+}
+
+/// Return an empty collection of the same type as the argument.
+#[doc(hidden)]
+pub fn __empty_like<M: Default>(_: &M) -> M {
+    M::default()
+}
+
+#[macro_export(local_inner_macros)]
+/// Move the values of a map to new keys.
+///
+/// `rename_keys!(map; old => new, ...)` applies the renames in order: the
+/// value for `old`, if present, is removed and inserted under `new`. The
+/// old keys are borrowed forms, like `"a"` or `&1`, and the new keys are
+/// owned keys. It returns the number of entries that were renamed.
+///
+/// By default a renamed entry replaces an existing entry for the new key.
+/// A policy can be given before the renames:
+///
+/// - `@keep;` leaves an entry under its old key if the new key is taken.
+/// - `@unique;` panics if the new key is taken.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let mut record = hashmap!{"legacy_id" => 7, "userName" => 1, "id" => 0};
+/// let renamed = rename_keys!(record; @keep;
+///     "legacy_id" => "id",
+///     "userName" => "user_name",
+/// );
+/// assert_eq!(renamed, 1);
+/// assert_eq!(record, hashmap!{"legacy_id" => 7, "user_name" => 1, "id" => 0});
+/// # }
+/// ```
+macro_rules! rename_keys {
+    ($map:expr; @keep; $($old:expr => $new:expr),* $(,)*) => {
+        {
+            let _map = &mut $map;
+            let mut _count = 0usize;
+            $(
+                let _new = $new;
+                if !_map.contains_key(&_new) {
+                    if let ::std::option::Option::Some(_v) = _map.remove($old) {
+                        let _ = _map.insert(_new, _v);
+                        _count += 1;
+                    }
+                }
+            )*
+            _count
+        }
+    };
+    ($map:expr; @unique; $($old:expr => $new:expr),* $(,)*) => {
+        {
+            let _map = &mut $map;
+            let mut _count = 0usize;
+            $(
+                if let ::std::option::Option::Some(_v) = _map.remove($old) {
+                    if _map.insert($new, _v).is_some() {
+                        ::std::panic!("rename_keys!: key `{}` already exists", ::std::stringify!($new));
+                    }
+                    _count += 1;
+                }
+            )*
+            _count
+        }
+    };
+    ($map:expr; $($old:expr => $new:expr),* $(,)*) => {
+        {
+            let _map = &mut $map;
+            let mut _count = 0usize;
+            $(
+                if let ::std::option::Option::Some(_v) = _map.remove($old) {
+                    let _ = _map.insert($new, _v);
+                    _count += 1;
+                }
+            )*
+            _count
+        }
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Join two maps by key into a **HashMap** of value pairs.
+///
+/// The maps are taken by value. The first can be anything that iterates as
+/// key-value pairs, and the second needs a `remove(&key)` method, like
+/// `HashMap` and `BTreeMap` have.
+///
+/// - `map_zip!(a, b)` keeps the keys present in both, with values `(A, B)`.
+/// - `map_zip!(@left; a, b)` keeps the keys of `a`, with values
+///   `(A, Option<B>)`.
+/// - `map_zip!(@outer; a, b)` keeps the keys of either, with values
+///   `(Option<A>, Option<B>)`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let names = hashmap!{1 => "ann", 2 => "bo"};
+/// let ages = hashmap!{1 => 31, 3 => 40};
+/// assert_eq!(map_zip!(names.clone(), ages.clone()), hashmap!{1 => ("ann", 31)});
+/// assert_eq!(map_zip!(@left; names.clone(), ages.clone()),
+///            hashmap!{1 => ("ann", Some(31)), 2 => ("bo", None)});
+/// assert_eq!(map_zip!(@outer; names, ages)[&3], (None, Some(40)));
+/// # }
+/// ```
+macro_rules! map_zip {
+    (@left; $a:expr, $b:expr $(,)*) => {
+        {
+            let mut _b = $b;
+            let _zipped: ::std::collections::HashMap<_, _> = ::std::iter::IntoIterator::into_iter($a)
+                .map(|(_k, _va)| {
+                    let _vb = _b.remove(&_k);
+                    (_k, (_va, _vb))
+                })
+                .collect();
+            _zipped
+        }
+    };
+    (@outer; $a:expr, $b:expr $(,)*) => {
+        {
+            let mut _b = $b;
+            let mut _zipped: ::std::collections::HashMap<_, _> = ::std::iter::IntoIterator::into_iter($a)
+                .map(|(_k, _va)| {
+                    let _vb = _b.remove(&_k);
+                    (_k, (::std::option::Option::Some(_va), _vb))
+                })
+                .collect();
+            _zipped.extend(::std::iter::IntoIterator::into_iter(_b)
+                .map(|(_k, _vb)| (_k, (::std::option::Option::None, ::std::option::Option::Some(_vb)))));
+            _zipped
+        }
+    };
+    ($a:expr, $b:expr $(,)*) => {
+        {
+            let mut _b = $b;
+            let _zipped: ::std::collections::HashMap<_, _> = ::std::iter::IntoIterator::into_iter($a)
+                .filter_map(|(_k, _va)| _b.remove(&_k).map(|_vb| (_k, (_va, _vb))))
+                .collect();
+            _zipped
+        }
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Group the items of an iterator into a **HashMap** of vectors.
+///
+/// `group_by!(items, key_fn)` calls `key_fn(&item)` for each item and
+/// collects the items with the same key into a `Vec`, in iteration order.
+/// See [`btree_group_by!`](macro.btree_group_by.html) for ordered groups.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let words = vec!["apple", "avocado", "banana"];
+/// let by_initial = group_by!(words, |w| w.as_bytes()[0]);
+/// assert_eq!(by_initial[&b'a'], ["apple", "avocado"]);
+/// # }
+/// ```
+macro_rules! group_by {
+    ($items:expr, $key_fn:expr $(,)*) => {
+        $crate::__group_by_hash($items, $key_fn)
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Group the items of an iterator into a **BTreeMap** of vectors.
+///
+/// This is [`group_by!`](macro.group_by.html) with the groups ordered by
+/// key.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let groups = btree_group_by!(1..7, |n| n % 3);
+/// assert_eq!(groups.into_iter().collect::<Vec<_>>(),
+///            [(0, vec![3, 6]), (1, vec![1, 4]), (2, vec![2, 5])]);
+/// # }
+/// ```
+macro_rules! btree_group_by {
+    ($items:expr, $key_fn:expr $(,)*) => {
+        $crate::__group_by_btree($items, $key_fn)
+    };
+}
+
+#[doc(hidden)]
+pub fn __group_by_hash<I, K, F>(items: I, mut key_fn: F)
+    -> std::collections::HashMap<K, Vec<I::Item>>
+    where I: IntoIterator, K: std::hash::Hash + Eq, F: FnMut(&I::Item) -> K
+{
+    let mut groups = std::collections::HashMap::new();
+    for item in items {
+        groups.entry(key_fn(&item)).or_insert_with(Vec::new).push(item);
+    }
+    groups
+}
+
+#[doc(hidden)]
+pub fn __group_by_btree<I, K, F>(items: I, mut key_fn: F)
+    -> std::collections::BTreeMap<K, Vec<I::Item>>
+    where I: IntoIterator, K: Ord, F: FnMut(&I::Item) -> K
+{
+    let mut groups = std::collections::BTreeMap::new();
+    for item in items {
+        groups.entry(key_fn(&item)).or_insert_with(Vec::new).push(item);
+    }
+    groups
+}
+
+#[macro_export(local_inner_macros)]
+/// Index the items of an iterator by a unique key, in a **HashMap**.
+///
+/// - `index_by!(items, key_fn)` calls `key_fn(&item)` for each item, and
+///   panics if two items have the same key.
+/// - `index_by!(@result; items, key_fn)` returns `Result<HashMap<K, T>, K>`
+///   instead, with an error for the first repeated key.
+/// - `index_by!(@last; items, key_fn)` lets the last item with a key win.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// struct User { id: u32, name: &'static str }
+///
+/// let users = vec![User { id: 1, name: "ann" }, User { id: 2, name: "bo" }];
+/// let by_id = index_by!(users, |u| u.id);
+/// assert_eq!(by_id[&2].name, "bo");
+///
+/// let dupes = vec![User { id: 1, name: "ann" }, User { id: 1, name: "cy" }];
+/// assert!(index_by!(@result; dupes, |u| u.id).is_err());
+/// # }
+/// ```
+macro_rules! index_by {
+    (@result; $items:expr, $key_fn:expr $(,)*) => {
+        $crate::__index_by($items, $key_fn, false)
+    };
+    (@last; $items:expr, $key_fn:expr $(,)*) => {
+        match $crate::__index_by($items, $key_fn, true) {
+            ::std::result::Result::Ok(_map) => _map,
+            ::std::result::Result::Err(_) => ::std::unreachable!(),
+        }
+    };
+    ($items:expr, $key_fn:expr $(,)*) => {
+        match $crate::__index_by($items, $key_fn, false) {
+            ::std::result::Result::Ok(_map) => _map,
+            ::std::result::Result::Err(_) => ::std::panic!("index_by!: two items have the same key"),
+        }
+    };
+}
+
+#[doc(hidden)]
+pub fn __index_by<I, K, F>(items: I, mut key_fn: F, replace: bool)
+    -> Result<std::collections::HashMap<K, I::Item>, K>
+    where I: IntoIterator, K: std::hash::Hash + Eq, F: FnMut(&I::Item) -> K
+{
+    use std::collections::hash_map::{Entry, HashMap};
+
+    let items = items.into_iter();
+    let mut index = HashMap::with_capacity(items.size_hint().0);
+    for item in items {
+        match index.entry(key_fn(&item)) {
+            Entry::Occupied(mut e) => {
+                if !replace {
+                    return Err(e.remove_entry().0);
+                }
+                let _ = e.insert(item);
+            }
+            Entry::Vacant(e) => {
+                let _ = e.insert(item);
+            }
+        }
+    }
+    Ok(index)
+}
+
+#[macro_export(local_inner_macros)]
+/// Split a map in two by a predicate on its entries.
+///
+/// `partition_map!(map, pred)` takes the map by value and returns a pair of
+/// maps of the same type: the entries for which `pred(&key, &value)` is
+/// `true`, and the rest. It works with `HashMap`, `BTreeMap` and other maps
+/// that implement `Default` and `Extend`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let accounts = btreemap!{"ann" => true, "bo" => false, "cy" => true};
+/// let (active, inactive) = partition_map!(accounts, |_, &active| active);
+/// assert_eq!(active.keys().collect::<Vec<_>>(), [&"ann", &"cy"]);
+/// assert_eq!(inactive, btreemap!{"bo" => false});
+/// # }
+/// ```
+macro_rules! partition_map {
+    ($map:expr, $pred:expr $(,)*) => {
+        $crate::__partition_map($map, $pred)
+    };
+}
+
 #[macro_export(local_inner_macros)]
-/// Create a **HashMap** from a list of key-value pairs
+/// Split a set in two by a predicate on its elements.
+///
+/// This is [`partition_map!`](macro.partition_map.html) for sets, with a
+/// predicate `pred(&elem)`.
 ///
 /// ## Example
 ///
@@ -34,33 +3971,99 @@
 /// #[macro_use] extern crate maplit;
 /// # fn main() {
 ///
-/// let map = hashmap!{
-///     "a" => 1,
-///     "b" => 2,
-/// };
-/// assert_eq!(map["a"], 1);
-/// assert_eq!(map["b"], 2);
-/// assert_eq!(map.get("c"), None);
+/// let (even, odd) = partition_set!(hashset!{1, 2, 3, 4}, |n| n % 2 == 0);
+/// assert_eq!(even, hashset!{2, 4});
+/// assert_eq!(odd, hashset!{1, 3});
 /// # }
 /// ```
-macro_rules! hashmap {
-    (@single $($x:tt)*) => (());
-    (@count $($rest:expr),*) => (<[()]>::len(&[$(hashmap!(@single $rest)),*]));
+macro_rules! partition_set {
+    ($set:expr, $pred:expr $(,)*) => {
+        $crate::__partition_set($set, $pred)
+    };
+}
 
-    ($($key:expr => $value:expr,)+) => { hashmap!($($key => $value),+) };
-    ($($key:expr => $value:expr),*) => {
-        {
-            let _cap = hashmap!(@count $($key),*);
-            let mut _map = ::std::collections::HashMap::with_capacity(_cap);
-            $(
-                let _ = _map.insert($key, $value);
-            )*
-            _map
+#[doc(hidden)]
+pub fn __partition_map<M, K, V, F>(map: M, mut pred: F) -> (M, M)
+    where M: IntoIterator<Item=(K, V)> + Default + Extend<(K, V)>,
+          F: FnMut(&K, &V) -> bool
+{
+    let mut yes = M::default();
+    let mut no = M::default();
+    for (key, value) in map {
+        if pred(&key, &value) {
+            yes.extend(Some((key, value)));
+        } else {
+            no.extend(Some((key, value)));
         }
+    }
+    (yes, no)
+}
+
+#[doc(hidden)]
+pub fn __partition_set<S, T, F>(set: S, pred: F) -> (S, S)
+    where S: IntoIterator<Item=T> + Default + Extend<T>,
+          F: FnMut(&T) -> bool
+{
+    set.into_iter().partition(pred)
+}
+
+#[macro_export(local_inner_macros)]
+/// Create a **HashMap** from a list of keys and a list of values.
+///
+/// `zipmap!(keys; values)` pairs up the keys and values, which may be arrays
+/// or any other iterables, and panics if they have different lengths.
+/// `zipmap!(@truncate; keys; values)` stops at the end of the shorter one
+/// instead.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let map = zipmap!(["a", "b", "c"]; [1, 2, 3]);
+/// assert_eq!(map, hashmap!{"a" => 1, "b" => 2, "c" => 3});
+///
+/// let map = zipmap!(@truncate; 'a'..; 0..3);
+/// assert_eq!(map, hashmap!{'a' => 0, 'b' => 1, 'c' => 2});
+/// # }
+/// ```
+macro_rules! zipmap {
+    (@truncate; $keys:expr; $values:expr $(;)*) => {
+        $crate::__zipmap($keys, $values, true)
+    };
+    ($keys:expr; $values:expr $(;)*) => {
+        $crate::__zipmap($keys, $values, false)
     };
 }
 
-/// Create a **HashSet** from a list of elements.
+#[doc(hidden)]
+pub fn __zipmap<I, J>(keys: I, values: J, truncate: bool)
+    -> std::collections::HashMap<I::Item, J::Item>
+    where I: IntoIterator, J: IntoIterator, I::Item: std::hash::Hash + Eq
+{
+    let mut keys = keys.into_iter();
+    let mut values = values.into_iter();
+    let mut map = std::collections::HashMap::with_capacity(
+        keys.size_hint().0.min(values.size_hint().0));
+    loop {
+        match (keys.next(), values.next()) {
+            (Some(key), Some(value)) => {
+                let _ = map.insert(key, value);
+            }
+            (None, None) => break,
+            _ if truncate => break,
+            _ => panic!("zipmap!: the keys and values have different lengths"),
+        }
+    }
+    map
+}
+
+#[macro_export(local_inner_macros)]
+/// Create a **HashMap** from a list of keys, with each value computed from
+/// its key.
+///
+/// The syntax is `hashmap_fn!(f; key, ...)`, and each value is `f(&key)`.
 ///
 /// ## Example
 ///
@@ -68,32 +4071,236 @@ macro_rules! hashmap {
 /// #[macro_use] extern crate maplit;
 /// # fn main() {
 ///
-/// let set = hashset!{"a", "b"};
-/// assert!(set.contains("a"));
-/// assert!(set.contains("b"));
-/// assert!(!set.contains("c"));
+/// let lengths = hashmap_fn!(|k| k.len(); "alpha", "bet", "gamma");
+/// assert_eq!(lengths, hashmap!{"alpha" => 5, "bet" => 3, "gamma" => 5});
 /// # }
 /// ```
+macro_rules! hashmap_fn {
+    ($f:expr; $($key:expr),* $(,)*) => {
+        $crate::__hashmap_fn(<[_]>::into_vec(::std::boxed::Box::new([$($key),*])), $f)
+    };
+}
+
+#[doc(hidden)]
+pub fn __hashmap_fn<K, V, F>(keys: Vec<K>, mut f: F) -> std::collections::HashMap<K, V>
+    where K: std::hash::Hash + Eq, F: FnMut(&K) -> V
+{
+    let mut map = std::collections::HashMap::with_capacity(keys.len());
+    for key in keys {
+        let value = f(&key);
+        let _ = map.insert(key, value);
+    }
+    map
+}
+
 #[macro_export(local_inner_macros)]
-macro_rules! hashset {
-    (@single $($x:tt)*) => (());
-    (@count $($rest:expr),*) => (<[()]>::len(&[$(hashset!(@single $rest)),*]));
+/// Count the items of an iterator into a **HashMap** from item to count.
+///
+/// The map is allocated using the iterator's size hint. See
+/// [`btree_frequency!`](macro.btree_frequency.html) for counts ordered by
+/// item, and [`counter!`](macro.counter.html) for counting literals.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let text = "the cat and the hat";
+/// let counts = frequency!(text.split(' '));
+/// assert_eq!(counts["the"], 2);
+/// assert_eq!(counts["cat"], 1);
+/// # }
+/// ```
+macro_rules! frequency {
+    ($items:expr $(,)*) => ($crate::__frequency_hash($items));
+}
 
-    ($($key:expr,)+) => { hashset!($($key),+) };
-    ($($key:expr),*) => {
+#[macro_export(local_inner_macros)]
+/// Count the items of an iterator into a **BTreeMap** from item to count.
+///
+/// This is [`frequency!`](macro.frequency.html) with the counts ordered by
+/// item.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let counts = btree_frequency!(vec![3, 1, 3, 2, 3]);
+/// assert_eq!(counts.into_iter().collect::<Vec<_>>(), [(1, 1), (2, 1), (3, 3)]);
+/// # }
+/// ```
+macro_rules! btree_frequency {
+    ($items:expr $(,)*) => ($crate::__frequency_btree($items));
+}
+
+#[doc(hidden)]
+pub fn __frequency_hash<I>(items: I) -> std::collections::HashMap<I::Item, usize>
+    where I: IntoIterator, I::Item: std::hash::Hash + Eq
+{
+    let items = items.into_iter();
+    let mut counts = std::collections::HashMap::with_capacity(items.size_hint().0);
+    for item in items {
+        *counts.entry(item).or_insert(0) += 1;
+    }
+    counts
+}
+
+#[doc(hidden)]
+pub fn __frequency_btree<I>(items: I) -> std::collections::BTreeMap<I::Item, usize>
+    where I: IntoIterator, I::Item: Ord
+{
+    let mut counts = std::collections::BTreeMap::new();
+    for item in items {
+        *counts.entry(item).or_insert(0) += 1;
+    }
+    counts
+}
+
+#[macro_export(local_inner_macros)]
+/// Assert that two maps are equal, and describe the differences if not.
+///
+/// On failure, the panic message lists the keys that are only in one of the
+/// maps and the keys whose values differ, sorted by the `Debug` form of the
+/// key. The maps must implement `PartialEq`, and their keys and values
+/// `Debug`. An optional format string and arguments are added to the
+/// message, like for `assert_eq!`.
+///
+/// ## Example
+///
+/// ```should_panic
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let expected = hashmap!{"a" => 1, "b" => 2};
+/// let actual = hashmap!{"b" => 3, "c" => 4};
+/// assert_map_eq!(actual, expected);
+/// // panics with:
+/// // assertion failed: `left == right` (maps differ)
+/// //   different values for "b": left 3, right 2
+/// //   only in left: "c" => 4
+/// //   only in right: "a" => 1
+/// # }
+/// ```
+macro_rules! assert_map_eq {
+    ($left:expr, $right:expr $(,)*) => {
+        match (&$left, &$right) {
+            (_left, _right) => {
+                if !(*_left == *_right) {
+                    ::std::panic!("{}", $crate::__map_diff_message(_left, _right));
+                }
+            }
+        }
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        match (&$left, &$right) {
+            (_left, _right) => {
+                if !(*_left == *_right) {
+                    ::std::panic!("{}: {}", $crate::__map_diff_message(_left, _right),
+                                  ::std::format_args!($($arg)+));
+                }
+            }
+        }
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Assert that two sets are equal, and describe the differences if not.
+///
+/// On failure, the panic message lists the elements that are only in the
+/// left or only in the right set, sorted by their `Debug` form. The sets
+/// must implement `PartialEq`, and their elements `Debug`. An optional
+/// format string and arguments are added to the message, like for
+/// `assert_eq!`.
+///
+/// ## Example
+///
+/// ```should_panic
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// assert_set_eq!(hashset!{1, 2, 3, 4}, hashset!{2, 3, 7});
+/// // panics with:
+/// // assertion failed: `left == right` (sets differ)
+/// //   only in left: [1, 4]
+/// //   only in right: [7]
+/// # }
+/// ```
+macro_rules! assert_set_eq {
+    ($left:expr, $right:expr $(,)*) => {
+        match (&$left, &$right) {
+            (_left, _right) => {
+                if !(*_left == *_right) {
+                    ::std::panic!("{}", $crate::__set_diff_message(_left, _right));
+                }
+            }
+        }
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        match (&$left, &$right) {
+            (_left, _right) => {
+                if !(*_left == *_right) {
+                    ::std::panic!("{}: {}", $crate::__set_diff_message(_left, _right),
+                                  ::std::format_args!($($arg)+));
+                }
+            }
+        }
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Assert that a map contains at least the listed entries.
+///
+/// The syntax is `assert_submap!(map, { key => value, ... })`. Other entries
+/// of the map are ignored. The keys are borrowed forms, like `"a"` or `&1`,
+/// that are passed to `map.get`. On failure, the panic message lists the
+/// missing keys and the keys with different values.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let response = hashmap!{"status" => "200", "body" => "ok", "date" => "today"};
+/// assert_submap!(response, {"status" => "200", "body" => "ok"});
+/// # }
+/// ```
+macro_rules! assert_submap {
+    ($map:expr, { $($key:expr => $value:expr),* $(,)* } $(,)*) => {
         {
-            let _cap = hashset!(@count $($key),*);
-            let mut _set = ::std::collections::HashSet::with_capacity(_cap);
+            let _map = &$map;
+            let mut _msg = ::std::string::String::new();
             $(
-                let _ = _set.insert($key);
+                let _key = $key;
+                match _map.get(_key) {
+                    ::std::option::Option::None => {
+                        _msg.push_str(&::std::format!("\n  missing key: {:?}", _key));
+                    }
+                    ::std::option::Option::Some(_v) => {
+                        let _value = $value;
+                        if *_v != _value {
+                            _msg.push_str(&::std::format!("\n  different value for {:?}: map {:?}, expected {:?}",
+                                                          _key, _v, _value));
+                        }
+                    }
+                }
             )*
-            _set
+            if !_msg.is_empty() {
+                ::std::panic!("assertion failed: map doesn't contain the expected entries{}", _msg);
+            }
         }
     };
 }
 
 #[macro_export(local_inner_macros)]
-/// Create a **BTreeMap** from a list of key-value pairs
+/// Assert that a map contains at least the listed keys.
+///
+/// The syntax is `assert_contains_keys!(map, key, ...)`, with keys in
+/// borrowed form, like `"a"` or `&1`, that are passed to
+/// `map.contains_key`. On failure, the panic message lists the missing keys.
 ///
 /// ## Example
 ///
@@ -101,32 +4308,34 @@ macro_rules! hashset {
 /// #[macro_use] extern crate maplit;
 /// # fn main() {
 ///
-/// let map = btreemap!{
-///     "a" => 1,
-///     "b" => 2,
-/// };
-/// assert_eq!(map["a"], 1);
-/// assert_eq!(map["b"], 2);
-/// assert_eq!(map.get("c"), None);
+/// let headers = btreemap!{"date" => 1, "server" => 2, "etag" => 3};
+/// assert_contains_keys!(headers, "date", "etag");
 /// # }
 /// ```
-macro_rules! btreemap {
-    // trailing comma case
-    ($($key:expr => $value:expr,)+) => (btreemap!($($key => $value),+));
-
-    ( $($key:expr => $value:expr),* ) => {
+macro_rules! assert_contains_keys {
+    ($map:expr, $($key:expr),* $(,)*) => {
         {
-            let mut _map = ::std::collections::BTreeMap::new();
+            let _map = &$map;
+            let mut _missing = ::std::vec::Vec::<::std::string::String>::new();
             $(
-                let _ = _map.insert($key, $value);
+                let _key = $key;
+                if !_map.contains_key(_key) {
+                    _missing.push(::std::format!("{:?}", _key));
+                }
             )*
-            _map
+            if !_missing.is_empty() {
+                ::std::panic!("assertion failed: map doesn't contain the keys [{}]", _missing.join(", "));
+            }
         }
     };
 }
 
 #[macro_export(local_inner_macros)]
-/// Create a **BTreeSet** from a list of elements.
+/// Compute the difference between an old and a new map, as a
+/// [`MapDiff`](map_diff/struct.MapDiff.html) of references into the maps.
+///
+/// The maps need `get` and `contains_key` methods, like `HashMap` and
+/// `BTreeMap` have, and values that implement `PartialEq`.
 ///
 /// ## Example
 ///
@@ -134,22 +4343,259 @@ macro_rules! btreemap {
 /// #[macro_use] extern crate maplit;
 /// # fn main() {
 ///
-/// let set = btreeset!{"a", "b"};
-/// assert!(set.contains("a"));
-/// assert!(set.contains("b"));
-/// assert!(!set.contains("c"));
+/// let desired = btreemap!{"web" => 3, "worker" => 2, "cron" => 1};
+/// let actual = btreemap!{"web" => 3, "worker" => 1, "legacy" => 1};
+/// let diff = map_diff!(actual, desired);
+/// assert_eq!(diff.added, [(&"cron", &1)]);
+/// assert_eq!(diff.removed, [(&"legacy", &1)]);
+/// assert_eq!(diff.changed, [(&"worker", &1, &2)]);
 /// # }
 /// ```
-macro_rules! btreeset {
-    ($($key:expr,)+) => (btreeset!($($key),+));
+macro_rules! map_diff {
+    ($old:expr, $new:expr $(,)*) => {
+        {
+            let _old = &$old;
+            let _new = &$new;
+            let mut _diff = $crate::MapDiff::new();
+            for (_k, _v) in _old {
+                match _new.get(_k) {
+                    ::std::option::Option::None => _diff.removed.push((_k, _v)),
+                    ::std::option::Option::Some(_w) => {
+                        if *_v != *_w {
+                            _diff.changed.push((_k, _v, _w));
+                        }
+                    }
+                }
+            }
+            for (_k, _v) in _new {
+                if !_old.contains_key(_k) {
+                    _diff.added.push((_k, _v));
+                }
+            }
+            _diff
+        }
+    };
+}
 
-    ( $($key:expr),* ) => {
+#[macro_export(local_inner_macros)]
+/// Print a `HashMap` or `HashSet` to stderr with its entries sorted, and
+/// return it, like `dbg!`.
+///
+/// The output is `[file:line] expression = value`, with the value formatted
+/// as `{:#?}` of [`SortedDebug`](struct.SortedDebug.html).
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let map = dbg_map!(hashmap!{"b" => 2, "a" => 1});
+/// // prints:
+/// // [src/main.rs:5] hashmap!{"b" => 2, "a" => 1} = {
+/// //     "a": 1,
+/// //     "b": 2,
+/// // }
+/// assert_eq!(map.len(), 2);
+/// # }
+/// ```
+macro_rules! dbg_map {
+    ($map:expr $(,)*) => {
+        match $map {
+            _map => {
+                ::std::eprintln!("[{}:{}] {} = {:#?}", ::std::file!(), ::std::line!(),
+                                 ::std::stringify!($map), $crate::SortedDebug(&_map));
+                _map
+            }
+        }
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Check whether a map has the listed keys, with values that match the
+/// listed patterns.
+///
+/// The syntax is `matches_map!(map, { key => pattern, ... })`, and it
+/// returns a `bool`. Other entries of the map are ignored. A key is looked
+/// up with `map.get(key)`, so keys are borrowed forms like `"a"` or `&1`,
+/// and the pattern `_` only checks that the key is present.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// let response = hashmap!{"status" => 200, "length" => 12};
+/// assert!(matches_map!(response, {"status" => 200 | 204, "length" => _}));
+/// assert!(!matches_map!(response, {"status" => 200, "body" => _}));
+/// assert!(matches_map!(response, {"length" => 1..=100}));
+/// # }
+/// ```
+macro_rules! matches_map {
+    ($map:expr, { $($key:expr => $($pat:pat)|+),* $(,)* } $(,)*) => {
+        {
+            let _map = &$map;
+            true $(&& match _map.get($key) {
+                ::std::option::Option::Some($($pat)|+) => true,
+                _ => false,
+            })*
+        }
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Create a **HashMap** of handler functions, for dispatching on a key.
+///
+/// `dispatch_table!(fn(Args) -> R; key => handler, ...)` casts every handler
+/// to the function pointer type `fn(Args) -> R`. Each function has its own
+/// type, so without the cast a map of two functions fails to compile with
+/// "expected fn item, found a different fn item". Closures that don't
+/// capture anything are cast too, and their parameter types are inferred
+/// from the function pointer type.
+///
+/// `dispatch_table!(@boxed dyn Fn(Args) -> R; key => handler, ...)` boxes
+/// every handler as a trait object instead, so the handlers can be closures
+/// that capture their environment. Their parameter types are inferred from
+/// the trait as well.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// # fn main() {
+///
+/// fn add(a: i64, b: i64) -> i64 { a + b }
+/// fn sub(a: i64, b: i64) -> i64 { a - b }
+///
+/// let ops = dispatch_table!(fn(i64, i64) -> i64;
+///     "+" => add,
+///     "-" => sub,
+///     "*" => |a, b| a * b,
+/// );
+/// assert_eq!(ops["-"](5, 3), 2);
+/// assert_eq!(ops["*"](5, 3), 15);
+///
+/// let greeting = String::from("hello");
+/// let commands = dispatch_table!(@boxed dyn Fn(&str) -> String;
+///     "greet" => move |name| format!("{}, {}", greeting, name),
+///     "shout" => |name| name.to_uppercase(),
+/// );
+/// assert_eq!(commands["greet"]("ann"), "hello, ann");
+/// # }
+/// ```
+macro_rules! dispatch_table {
+    // The boxed handler is bound with its type rather than cast, so that the
+    // parameter types of a closure are inferred from the trait.
+    (@boxed $tr:ty; $($key:expr => $handler:expr),* $(,)*) => {
+        hashmap!(@build $($key => {
+            let _handler: ::std::boxed::Box<$tr> = ::std::boxed::Box::new($handler);
+            _handler
+        }),*)
+    };
+    ($fn_type:ty; $($key:expr => $handler:expr),* $(,)*) => {
+        hashmap!(@build $($key => $handler as $fn_type),*)
+    };
+}
+
+#[macro_export(local_inner_macros)]
+/// Create a `HashMap<String, Value>` tree from nested literals.
+///
+/// Each entry is `key => value`, where the key converts to `String`. A value
+/// in braces `{ ... }` is a nested map, a value in brackets `[ ... ]` is a
+/// list of values, and `null` is [`Value::Null`](enum.Value.html). Any
+/// other value is an expression that is converted with `Value::from`, like
+/// a bool, a number or a string.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// use maplit::Value;
+///
+/// # fn main() {
+/// let port = 8080;
+/// let config = value_map!{
+///     "server" => {
+///         "port" => port,
+///         "hosts" => ["a.example", "b.example"],
+///         "timeout" => -1.5,
+///     },
+///     "debug" => false,
+///     "proxy" => null,
+/// };
+/// assert_eq!(config["server"].get("port"), Some(&Value::Int(8080)));
+/// assert_eq!(config["server"].get("hosts").and_then(Value::as_list).map(<[_]>::len), Some(2));
+/// assert!(config["proxy"].is_null());
+/// # }
+/// ```
+macro_rules! value_map {
+    (@value null) => ($crate::Value::Null);
+    (@value {$($map:tt)*}) => ($crate::Value::Map(value_map!($($map)*)));
+    (@value [$($item:tt),* $(,)*]) => {
+        $crate::Value::List(<[_]>::into_vec(::std::boxed::Box::new([
+            $(value_map!(@value $item)),*
+        ])))
+    };
+    (@value [$($list:tt)*]) => {
+        {
+            let mut _list = ::std::vec::Vec::new();
+            value_map!(@list _list; $($list)*);
+            $crate::Value::List(_list)
+        }
+    };
+    (@value $value:expr) => ($crate::Value::from($value));
+
+    // A value that is a single token tree is passed on as it is, so that
+    // braces and brackets are not parsed as expressions. Lists and maps
+    // where every value is a single token tree are matched by one
+    // repetition; the others are taken apart one entry at a time.
+    (@list $list:ident;) => {};
+    (@list $list:ident; $value:tt, $($rest:tt)*) => {
+        $list.push(value_map!(@value $value));
+        value_map!(@list $list; $($rest)*);
+    };
+    (@list $list:ident; $value:tt) => {
+        $list.push(value_map!(@value $value));
+    };
+    (@list $list:ident; $value:expr, $($rest:tt)*) => {
+        $list.push(value_map!(@value $value));
+        value_map!(@list $list; $($rest)*);
+    };
+    (@list $list:ident; $value:expr) => {
+        $list.push(value_map!(@value $value));
+    };
+
+    (@map $map:ident;) => {};
+    (@map $map:ident; $key:expr => $value:tt, $($rest:tt)*) => {
+        let _ = $map.insert(::std::string::String::from($key), value_map!(@value $value));
+        value_map!(@map $map; $($rest)*);
+    };
+    (@map $map:ident; $key:expr => $value:tt) => {
+        let _ = $map.insert(::std::string::String::from($key), value_map!(@value $value));
+    };
+    (@map $map:ident; $key:expr => $value:expr, $($rest:tt)*) => {
+        let _ = $map.insert(::std::string::String::from($key), value_map!(@value $value));
+        value_map!(@map $map; $($rest)*);
+    };
+    (@map $map:ident; $key:expr => $value:expr) => {
+        let _ = $map.insert(::std::string::String::from($key), value_map!(@value $value));
+    };
+
+    ($($key:expr => $value:tt),* $(,)*) => {
         {
-            let mut _set = ::std::collections::BTreeSet::new();
+            let mut _map = ::std::collections::HashMap::<::std::string::String, $crate::Value>::new();
             $(
-                _set.insert($key);
+                let _ = _map.insert(::std::string::String::from($key), value_map!(@value $value));
             )*
-            _set
+            _map
+        }
+    };
+    ($($rest:tt)*) => {
+        {
+            let mut _map = ::std::collections::HashMap::<::std::string::String, $crate::Value>::new();
+            value_map!(@map _map; $($rest)*);
+            _map
         }
     };
 }
@@ -170,6 +4616,10 @@ pub fn __id<T>(t: T) -> T { t }
 /// Here *macro_name* is any other maplit macro and either or both of the
 /// explicit `keys=` and `values=` parameters can be omitted.
 ///
+/// Instead of a function, a conversion can be a primitive cast written
+/// `as` *type*, for example `keys = as u64`. This covers numeric
+/// conversions that `Into` doesn't, like `usize` to `u64` or `i64` to `f64`.
+///
 /// [`Into`]: https://doc.rust-lang.org/std/convert/trait.Into.html
 ///
 /// **Note** To use `convert_args`, the macro that is being wrapped
@@ -213,11 +4663,26 @@ pub fn __id<T>(t: T) -> T { t }
 /// ));
 /// assert_eq!(set.len(), 6);
 ///
+/// // d. Use primitive casts instead of functions.
+///
+/// let map3 = convert_args!(keys = as u64, values = as f64, hashmap!(
+///     1usize => 2i64,
+/// ));
+/// let _: HashMap<u64, f64> = map3;
 ///
 /// # }
 /// ```
 #[macro_export(local_inner_macros)]
 macro_rules! convert_args {
+    (keys = as $kt:ty, $($rest:tt)*) => {
+        convert_args! { keys=|_k| _k as $kt, $($rest)* }
+    };
+    (values = as $vt:ty, $($rest:tt)*) => {
+        convert_args! { values=|_v| _v as $vt, $($rest)* }
+    };
+    (keys=$kf:expr, values = as $vt:ty, $($rest:tt)*) => {
+        convert_args! { keys=$kf, values=|_v| _v as $vt, $($rest)* }
+    };
     (keys=$kf:expr, $macro_name:ident !($($k:expr),* $(,)*)) => {
         $macro_name! { $(($kf)($k)),* }
     };
@@ -322,3 +4787,13 @@ fn test_btreemap() {
         2 => btreemap!{1 => 1,},
     };
 }
+
+#[test]
+fn test_btreeset() {
+    use std::collections::BTreeSet;
+    let set = btreeset!{3, 1, 2, 1,};
+    assert_eq!(set.iter().cloned().collect::<Vec<_>>(), [1, 2, 3]);
+
+    let empty: BTreeSet<i32> = btreeset!{};
+    assert!(empty.is_empty());
+}
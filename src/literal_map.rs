@@ -0,0 +1,149 @@
+//! A small map stored as a vector of key-value pairs.
+
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+use std::iter::FromIterator;
+use std::slice;
+use std::vec;
+
+/// A small map stored as a vector of key-value pairs, with linear lookup.
+///
+/// For maps of up to about 16 entries, a linear scan is often faster than
+/// hashing the key. Entries are kept in insertion order.
+///
+/// Created with the [`literalmap!`](../macro.literalmap.html) macro or by
+/// collecting from an iterator, and converts into `HashMap` and `BTreeMap`
+/// with [`into_hashmap`](#method.into_hashmap) and
+/// [`into_btreemap`](#method.into_btreemap).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LiteralMap<K, V> {
+    entries: Vec<(K, V)>,
+}
+
+impl<K, V> LiteralMap<K, V> {
+    /// Create a new, empty map.
+    pub fn new() -> Self {
+        LiteralMap { entries: Vec::new() }
+    }
+
+    /// Create a new, empty map with room for `capacity` entries.
+    pub fn with_capacity(capacity: usize) -> Self {
+        LiteralMap { entries: Vec::with_capacity(capacity) }
+    }
+
+    /// Return the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return `true` if the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Return a reference to the value for `key`, if present.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+        where K: Borrow<Q>, Q: ?Sized + Eq
+    {
+        self.entries.iter().find(|e| e.0.borrow() == key).map(|e| &e.1)
+    }
+
+    /// Return a mutable reference to the value for `key`, if present.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+        where K: Borrow<Q>, Q: ?Sized + Eq
+    {
+        self.entries.iter_mut().find(|e| e.0.borrow() == key).map(|e| &mut e.1)
+    }
+
+    /// Return `true` if the map contains `key`.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where K: Borrow<Q>, Q: ?Sized + Eq
+    {
+        self.get(key).is_some()
+    }
+
+    /// Insert a key-value pair.
+    ///
+    /// If the key was already present, its value is replaced and the old
+    /// value is returned. New keys are appended at the end.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V>
+        where K: Eq
+    {
+        if let Some(slot) = self.entries.iter_mut().find(|e| e.0 == key) {
+            return Some(::std::mem::replace(&mut slot.1, value));
+        }
+        self.entries.push((key, value));
+        None
+    }
+
+    /// Return an iterator of the entries, in insertion order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter { iter: self.entries.iter() }
+    }
+
+    /// Convert into a `HashMap`.
+    pub fn into_hashmap<S>(self) -> HashMap<K, V, S>
+        where K: Hash + Eq, S: BuildHasher + Default
+    {
+        self.entries.into_iter().collect()
+    }
+
+    /// Convert into a `BTreeMap`.
+    pub fn into_btreemap(self) -> BTreeMap<K, V>
+        where K: Ord
+    {
+        self.entries.into_iter().collect()
+    }
+}
+
+impl<K, V> Default for LiteralMap<K, V> {
+    fn default() -> Self {
+        LiteralMap::new()
+    }
+}
+
+impl<K: Eq, V> FromIterator<(K, V)> for LiteralMap<K, V> {
+    fn from_iter<I>(iterable: I) -> Self
+        where I: IntoIterator<Item=(K, V)>
+    {
+        let mut map = LiteralMap::new();
+        for (k, v) in iterable {
+            let _ = map.insert(k, v);
+        }
+        map
+    }
+}
+
+impl<K, V> IntoIterator for LiteralMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = vec::IntoIter<(K, V)>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a LiteralMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the entries of a [`LiteralMap`](struct.LiteralMap.html).
+#[derive(Clone, Debug)]
+pub struct Iter<'a, K: 'a, V: 'a> {
+    iter: slice::Iter<'a, (K, V)>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|e| (&e.0, &e.1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
@@ -0,0 +1,38 @@
+//! The difference between two maps.
+
+/// The difference between an old and a new map, as computed by
+/// [`map_diff!`](../macro.map_diff.html).
+///
+/// Each list is in the iteration order of the map it comes from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MapDiff<K, V> {
+    /// Entries that are only in the new map.
+    pub added: Vec<(K, V)>,
+    /// Entries that are only in the old map.
+    pub removed: Vec<(K, V)>,
+    /// Keys in both maps with different values, as `(key, old, new)`.
+    pub changed: Vec<(K, V, V)>,
+}
+
+impl<K, V> MapDiff<K, V> {
+    /// Create an empty diff.
+    pub fn new() -> Self {
+        MapDiff { added: Vec::new(), removed: Vec::new(), changed: Vec::new() }
+    }
+
+    /// Return `true` if the maps were equal.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Return the number of differing keys.
+    pub fn len(&self) -> usize {
+        self.added.len() + self.removed.len() + self.changed.len()
+    }
+}
+
+impl<K, V> Default for MapDiff<K, V> {
+    fn default() -> Self {
+        MapDiff::new()
+    }
+}
@@ -0,0 +1,122 @@
+//! Nested maps addressed by separated key paths.
+
+use std::collections::{hash_map, HashMap};
+
+/// An entry of a [`PathMap`](struct.PathMap.html): either a value or a
+/// nested map.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PathNode<V> {
+    /// A value.
+    Value(V),
+    /// A nested map.
+    Map(PathMap<V>),
+}
+
+impl<V> PathNode<V> {
+    /// Return the value, if this is a value.
+    pub fn as_value(&self) -> Option<&V> {
+        match *self {
+            PathNode::Value(ref v) => Some(v),
+            PathNode::Map(_) => None,
+        }
+    }
+
+    /// Return the nested map, if this is a map.
+    pub fn as_map(&self) -> Option<&PathMap<V>> {
+        match *self {
+            PathNode::Value(_) => None,
+            PathNode::Map(ref m) => Some(m),
+        }
+    }
+}
+
+/// A tree of nested maps, where a path like `"server.tls.cert"` addresses a
+/// value through the nested maps `server` and `tls`.
+///
+/// The separator is `.` by default and can be changed with
+/// [`with_separator`](#method.with_separator).
+///
+/// Created with the [`pathmap!`](../macro.pathmap.html) macro.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PathMap<V> {
+    map: HashMap<String, PathNode<V>>,
+    separator: char,
+}
+
+impl<V> PathMap<V> {
+    /// Create a new, empty map using `.` as the separator.
+    pub fn new() -> Self {
+        PathMap::with_separator('.')
+    }
+
+    /// Create a new, empty map using `separator` to split paths.
+    pub fn with_separator(separator: char) -> Self {
+        PathMap { map: HashMap::new(), separator }
+    }
+
+    /// Return the number of entries at the top level.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Return `true` if the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Return the entry for a single top level `key`, without splitting it.
+    pub fn get(&self, key: &str) -> Option<&PathNode<V>> {
+        self.map.get(key)
+    }
+
+    /// Return the entry at `path`, if present.
+    pub fn get_path(&self, path: &str) -> Option<&PathNode<V>> {
+        let mut parts = path.split(self.separator);
+        let first = parts.next()?;
+        let mut node = self.map.get(first)?;
+        for part in parts {
+            node = node.as_map()?.map.get(part)?;
+        }
+        Some(node)
+    }
+
+    /// Return the value at `path`, if present and not a nested map.
+    pub fn get_value(&self, path: &str) -> Option<&V> {
+        self.get_path(path).and_then(PathNode::as_value)
+    }
+
+    /// Set the value at `path`, creating nested maps as needed, and return
+    /// the entry that was there before.
+    ///
+    /// A value in the way of the path is replaced by a nested map.
+    pub fn set_path(&mut self, path: &str, value: V) -> Option<PathNode<V>> {
+        let separator = self.separator;
+        let mut parts = path.split(separator);
+        let mut key = parts.next().unwrap_or("");
+        let mut map = self;
+        for part in parts {
+            let entry = map.map.entry(key.to_string())
+                .or_insert_with(|| PathNode::Map(PathMap::with_separator(separator)));
+            if let PathNode::Value(_) = *entry {
+                *entry = PathNode::Map(PathMap::with_separator(separator));
+            }
+            map = match *entry {
+                PathNode::Map(ref mut m) => m,
+                PathNode::Value(_) => unreachable!(),
+            };
+            key = part;
+        }
+        map.map.insert(key.to_string(), PathNode::Value(value))
+    }
+
+    /// Return an iterator of the top level entries, in arbitrary order.
+    pub fn iter(&self) -> hash_map::Iter<'_, String, PathNode<V>> {
+        self.map.iter()
+    }
+}
+
+impl<V> Default for PathMap<V> {
+    fn default() -> Self {
+        PathMap::new()
+    }
+}
@@ -0,0 +1,109 @@
+//! A static map with a perfect hash function computed at compile time.
+
+use std::borrow::Borrow;
+use std::hash::{Hash, Hasher};
+use std::slice;
+
+use fnv::FnvHasher;
+
+/// A read-only map whose entries are placed by a perfect hash function, so
+/// that a lookup hashes the key twice and compares it with one entry.
+///
+/// The hash function and the table are computed by the
+/// [`perfectmap!`](../macro.perfectmap.html) macro at compile time, and the
+/// map only borrows `'static` data, so it can be a `static` item.
+///
+/// Keys are looked up with hash-and-displace: the first hash picks a bucket,
+/// the bucket's displacement seeds the second hash, and the second hash is
+/// the index of the entry.
+#[derive(Clone, Copy, Debug)]
+pub struct PerfectMap<K: 'static, V: 'static> {
+    displacements: &'static [u32],
+    entries: &'static [(K, V)],
+}
+
+/// Hash `key` with `FnvHasher`, after feeding it `seed`.
+///
+/// The low bits of an FNV hash depend on few input bits, so the hash is
+/// mixed before it is reduced to a bucket or an index.
+fn seeded_hash<Q: ?Sized + Hash>(seed: u32, key: &Q) -> u64 {
+    let mut hasher = FnvHasher::default();
+    hasher.write_u32(seed);
+    key.hash(&mut hasher);
+    let hash = hasher.finish();
+    let hash = (hash ^ (hash >> 33)).wrapping_mul(0xff51_afd7_ed55_8ccd);
+    hash ^ (hash >> 33)
+}
+
+impl<K, V> PerfectMap<K, V> {
+    /// Create a map from the tables computed by `perfectmap!`.
+    #[doc(hidden)]
+    pub const fn __new(displacements: &'static [u32], entries: &'static [(K, V)]) -> Self {
+        PerfectMap { displacements, entries }
+    }
+
+    /// Return the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return `true` if the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Return a reference to the value for `key`, if present.
+    pub fn get<Q>(&self, key: &Q) -> Option<&'static V>
+        where K: Borrow<Q>, Q: ?Sized + Hash + Eq
+    {
+        if self.entries.is_empty() {
+            return None;
+        }
+        let bucket = seeded_hash(0, key) % self.displacements.len() as u64;
+        let seed = self.displacements[bucket as usize];
+        let index = seeded_hash(seed, key) % self.entries.len() as u64;
+        let entry = &self.entries[index as usize];
+        if entry.0.borrow() == key {
+            Some(&entry.1)
+        } else {
+            None
+        }
+    }
+
+    /// Return `true` if the map contains `key`.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where K: Borrow<Q>, Q: ?Sized + Hash + Eq
+    {
+        self.get(key).is_some()
+    }
+
+    /// Return an iterator of the entries, in arbitrary order.
+    pub fn iter(&self) -> Iter<K, V> {
+        Iter { iter: self.entries.iter() }
+    }
+}
+
+impl<K, V> IntoIterator for PerfectMap<K, V> {
+    type Item = (&'static K, &'static V);
+    type IntoIter = Iter<K, V>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the entries of a [`PerfectMap`](struct.PerfectMap.html).
+#[derive(Clone, Debug)]
+pub struct Iter<K: 'static, V: 'static> {
+    iter: slice::Iter<'static, (K, V)>,
+}
+
+impl<K, V> Iterator for Iter<K, V> {
+    type Item = (&'static K, &'static V);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|e| (&e.0, &e.1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
@@ -0,0 +1,30 @@
+//! Maps whose literal keys are hashed at compile time.
+//!
+//! Created with the [`prehashed_map!`](../macro.prehashed_map.html) macro.
+
+use std::hash::{BuildHasher, Hash};
+
+use __hashbrown::hash_map::RawEntryMut;
+use fnv::FnvBuildHasher;
+
+/// A hashbrown **HashMap** using [`FnvHasher`](../struct.FnvHasher.html),
+/// the hasher that `prehashed_map!` computes its key hashes with.
+pub type PrehashedMap<K, V> = __hashbrown::HashMap<K, V, FnvBuildHasher>;
+
+/// Insert a key-value pair with a precomputed hash, replacing the value if
+/// the key is already present.
+#[doc(hidden)]
+pub fn __insert_prehashed<K, V>(map: &mut PrehashedMap<K, V>, hash: u64, key: K, value: V)
+    where K: Eq + Hash
+{
+    debug_assert_eq!(hash, map.hasher().hash_one(&key),
+                     "prehashed_map!: wrong precomputed hash");
+    match map.raw_entry_mut().from_key_hashed_nocheck(hash, &key) {
+        RawEntryMut::Occupied(mut e) => {
+            let _ = e.insert(value);
+        }
+        RawEntryMut::Vacant(e) => {
+            let _ = e.insert_hashed_nocheck(hash, key, value);
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! A set of values stored as coalesced ranges.
+
+use std::ops::{Bound, RangeBounds, RangeInclusive};
+use std::slice;
+
+/// A discrete, ordered type with a minimum and maximum value, like the
+/// primitive integers.
+///
+/// This is what lets a [`RangeSet`](struct.RangeSet.html) treat `0..10` and
+/// `0..=9` as the same range, and merge adjacent ranges.
+pub trait Discrete: Ord + Copy {
+    /// The smallest value.
+    const MIN: Self;
+    /// The largest value.
+    const MAX: Self;
+    /// Return the next value, or `None` for `MAX`.
+    fn successor(self) -> Option<Self>;
+    /// Return the previous value, or `None` for `MIN`.
+    fn predecessor(self) -> Option<Self>;
+}
+
+macro_rules! impl_discrete {
+    ($($t:ident)*) => {
+        $(
+            impl Discrete for $t {
+                const MIN: $t = ::std::$t::MIN;
+                const MAX: $t = ::std::$t::MAX;
+                fn successor(self) -> Option<$t> { self.checked_add(1) }
+                fn predecessor(self) -> Option<$t> { self.checked_sub(1) }
+            }
+        )*
+    };
+}
+
+impl_discrete!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+
+/// A set of values stored as a sorted list of disjoint, non-adjacent
+/// inclusive ranges.
+///
+/// Membership tests use binary search over the ranges, so a few large ranges
+/// take up little space no matter how many values they cover.
+///
+/// The values must implement [`Discrete`](trait.Discrete.html), not just
+/// `Ord`, because turning `0..10` into `0..=9` and merging it with `10..12`
+/// needs the value before and after a bound. Only the primitive integer
+/// types implement it, so ranges of strings, floats or other ordered types
+/// are not supported.
+///
+/// Created with the [`rangeset!`](macro.rangeset.html) macro.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    ranges: Vec<(T, T)>,
+}
+
+impl<T: Discrete> RangeSet<T> {
+    /// Create a new, empty set.
+    pub fn new() -> Self {
+        RangeSet { ranges: Vec::new() }
+    }
+
+    /// Return `true` if the set has no values.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Add all the values of `range` to the set.
+    ///
+    /// Any kind of range works, including `a..b`, `a..=b`, `a..` and `..`.
+    pub fn insert<R: RangeBounds<T>>(&mut self, range: R) {
+        let start = match range.start_bound() {
+            Bound::Included(&a) => Some(a),
+            Bound::Excluded(&a) => a.successor(),
+            Bound::Unbounded => Some(T::MIN),
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&b) => Some(b),
+            Bound::Excluded(&b) => b.predecessor(),
+            Bound::Unbounded => Some(T::MAX),
+        };
+        if let (Some(start), Some(end)) = (start, end) {
+            if start <= end {
+                self.ranges.push((start, end));
+                normalize(&mut self.ranges);
+            }
+        }
+    }
+
+    /// Return `true` if the set contains `value`.
+    pub fn contains(&self, value: &T) -> bool {
+        let i = match self.ranges.binary_search_by(|r| r.0.cmp(value)) {
+            Ok(_) => return true,
+            Err(i) => i,
+        };
+        i > 0 && *value <= self.ranges[i - 1].1
+    }
+
+    /// Return the union of this set and `other`.
+    pub fn union(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut ranges = self.ranges.clone();
+        ranges.extend_from_slice(&other.ranges);
+        normalize(&mut ranges);
+        RangeSet { ranges }
+    }
+
+    /// Return the intersection of this set and `other`.
+    pub fn intersection(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (self.ranges[i], other.ranges[j]);
+            let start = if a.0 > b.0 { a.0 } else { b.0 };
+            let end = if a.1 < b.1 { a.1 } else { b.1 };
+            if start <= end {
+                ranges.push((start, end));
+            }
+            if a.1 < b.1 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        RangeSet { ranges }
+    }
+
+    /// Return an iterator of the ranges of the set, in order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { iter: self.ranges.iter() }
+    }
+}
+
+impl<T: Discrete> Default for RangeSet<T> {
+    fn default() -> Self {
+        RangeSet::new()
+    }
+}
+
+impl<'a, T: Discrete> IntoIterator for &'a RangeSet<T> {
+    type Item = RangeInclusive<T>;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Sort the ranges and merge the ones that overlap or are adjacent.
+fn normalize<T: Discrete>(ranges: &mut Vec<(T, T)>) {
+    ranges.sort();
+    let mut merged: Vec<(T, T)> = Vec::with_capacity(ranges.len());
+    for &(start, end) in ranges.iter() {
+        if let Some(last) = merged.last_mut() {
+            let touches = match last.1.successor() {
+                Some(next) => start <= next,
+                None => true,
+            };
+            if touches {
+                if end > last.1 {
+                    last.1 = end;
+                }
+                continue;
+            }
+        }
+        merged.push((start, end));
+    }
+    *ranges = merged;
+}
+
+/// Iterator over the ranges of a [`RangeSet`](struct.RangeSet.html).
+#[derive(Clone, Debug)]
+pub struct Iter<'a, T: 'a> {
+    iter: slice::Iter<'a, (T, T)>,
+}
+
+impl<'a, T: Discrete> Iterator for Iter<'a, T> {
+    type Item = RangeInclusive<T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|&(start, end)| start..=end)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
@@ -0,0 +1,50 @@
+//! Debug formatting of hash maps and sets in sorted order.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// A wrapper whose `Debug` output lists the entries of a `HashMap` or the
+/// elements of a `HashSet` sorted by key, so that it is the same from run to
+/// run. Use `{:#?}` for multi-line output.
+///
+/// See also [`dbg_map!`](../macro.dbg_map.html).
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// use maplit::SortedDebug;
+///
+/// # fn main() {
+/// let map = hashmap!{"b" => 2, "a" => 1, "c" => 3};
+/// assert_eq!(format!("{:?}", SortedDebug(&map)), r#"{"a": 1, "b": 2, "c": 3}"#);
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+pub struct SortedDebug<'a, M: ?Sized + 'a>(pub &'a M);
+
+impl<'a, K, V, S> fmt::Debug for SortedDebug<'a, HashMap<K, V, S>>
+    where K: fmt::Debug + Ord, V: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut entries: Vec<_> = self.0.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        f.debug_map().entries(entries).finish()
+    }
+}
+
+impl<'a, T, S> fmt::Debug for SortedDebug<'a, HashSet<T, S>>
+    where T: fmt::Debug + Ord
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut elems: Vec<_> = self.0.iter().collect();
+        elems.sort();
+        f.debug_set().entries(elems).finish()
+    }
+}
+
+impl<'a, 'b, M: ?Sized> fmt::Debug for SortedDebug<'a, &'b M>
+    where SortedDebug<'b, M>: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        SortedDebug(*self.0).fmt(f)
+    }
+}
@@ -0,0 +1,164 @@
+//! A map stored as a sorted vector of key-value pairs.
+
+use std::borrow::Borrow;
+use std::iter::FromIterator;
+use std::slice;
+use std::vec;
+
+/// A map stored as a vector of key-value pairs sorted by key.
+///
+/// Lookups use binary search, and iteration is a scan of contiguous memory
+/// in key order. Inserting is `O(n)`, so this suits read-heavy tables that
+/// are built once.
+///
+/// Created with the [`sortedvecmap!`](../macro.sortedvecmap.html) macro or by
+/// collecting from an iterator; both sort the entries just once.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SortedVecMap<K, V> {
+    entries: Vec<(K, V)>,
+}
+
+impl<K: Ord, V> SortedVecMap<K, V> {
+    /// Create a new, empty map.
+    pub fn new() -> Self {
+        SortedVecMap { entries: Vec::new() }
+    }
+
+    /// Create a map from a vector of entries in any order.
+    ///
+    /// If a key is repeated, the last of its entries wins.
+    pub fn from_vec(mut entries: Vec<(K, V)>) -> Self {
+        // stable sort, so equal keys stay in input order
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        entries.reverse();
+        entries.dedup_by(|a, b| a.0 == b.0);
+        entries.reverse();
+        SortedVecMap { entries }
+    }
+
+    /// Return the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return `true` if the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn search<Q>(&self, key: &Q) -> Result<usize, usize>
+        where K: Borrow<Q>, Q: ?Sized + Ord
+    {
+        self.entries.binary_search_by(|e| e.0.borrow().cmp(key))
+    }
+
+    /// Return a reference to the value for `key`, if present.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+        where K: Borrow<Q>, Q: ?Sized + Ord
+    {
+        self.search(key).ok().map(|i| &self.entries[i].1)
+    }
+
+    /// Return a mutable reference to the value for `key`, if present.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+        where K: Borrow<Q>, Q: ?Sized + Ord
+    {
+        match self.search(key) {
+            Ok(i) => Some(&mut self.entries[i].1),
+            Err(_) => None,
+        }
+    }
+
+    /// Return `true` if the map contains `key`.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where K: Borrow<Q>, Q: ?Sized + Ord
+    {
+        self.search(key).is_ok()
+    }
+
+    /// Insert a key-value pair, returning the old value if the key was
+    /// already present.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.search(&key) {
+            Ok(i) => Some(::std::mem::replace(&mut self.entries[i].1, value)),
+            Err(i) => {
+                self.entries.insert(i, (key, value));
+                None
+            }
+        }
+    }
+
+    /// Remove `key` and return its value, if present.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+        where K: Borrow<Q>, Q: ?Sized + Ord
+    {
+        match self.search(key) {
+            Ok(i) => Some(self.entries.remove(i).1),
+            Err(_) => None,
+        }
+    }
+
+    /// Return an iterator of the entries, in key order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter { iter: self.entries.iter() }
+    }
+
+    /// Return the entries as a sorted slice.
+    pub fn as_slice(&self) -> &[(K, V)] {
+        &self.entries
+    }
+}
+
+impl<K: Ord, V> Default for SortedVecMap<K, V> {
+    fn default() -> Self {
+        SortedVecMap::new()
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for SortedVecMap<K, V> {
+    fn from_iter<I>(iterable: I) -> Self
+        where I: IntoIterator<Item=(K, V)>
+    {
+        SortedVecMap::from_vec(iterable.into_iter().collect())
+    }
+}
+
+impl<K, V> IntoIterator for SortedVecMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = vec::IntoIter<(K, V)>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'a, K: Ord, V> IntoIterator for &'a SortedVecMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the entries of a
+/// [`SortedVecMap`](struct.SortedVecMap.html).
+#[derive(Clone, Debug)]
+pub struct Iter<'a, K: 'a, V: 'a> {
+    iter: slice::Iter<'a, (K, V)>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|e| (&e.0, &e.1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Iter<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|e| (&e.0, &e.1))
+    }
+}
@@ -0,0 +1,54 @@
+//! Conversion of a struct into a map of its fields.
+
+use std::collections::{BTreeMap, HashMap};
+
+use value::Value;
+
+/// A struct that converts into a map from field names to values.
+///
+/// Derive it with `#[derive(ToMap)]`. Each field becomes an entry with the
+/// field name as its key and `Value::from` of a clone of the field as its
+/// value, so the field types must implement `Clone` and `Into<Value>`.
+///
+/// The `#[map(...)]` attribute changes how a field is converted:
+///
+/// - `#[map(rename = "name")]` uses `name` as the key.
+/// - `#[map(skip)]` leaves the field out of the map.
+///
+/// The attribute is shared with [`FromMap`](trait.FromMap.html), whose
+/// `default` option is ignored here.
+///
+/// Requires crate feature `"proc-macro"`.
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate maplit;
+/// use maplit::{ToMap, Value};
+///
+/// #[derive(ToMap)]
+/// struct User {
+///     name: String,
+///     #[map(rename = "years")]
+///     age: u32,
+///     #[map(skip)]
+///     password: String,
+/// }
+///
+/// # fn main() {
+/// let user = User { name: "ann".to_string(), age: 30, password: String::new() };
+/// let map = user.to_map();
+/// assert_eq!(map["years"], Value::Int(30));
+/// assert!(!map.contains_key("password"));
+/// # let _ = user.password;
+/// # }
+/// ```
+pub trait ToMap {
+    /// Return a map from the field names to the field values.
+    fn to_map(&self) -> HashMap<&'static str, Value>;
+
+    /// Return the map of [`to_map`](#tymethod.to_map), ordered by key.
+    fn to_btree_map(&self) -> BTreeMap<&'static str, Value> {
+        self.to_map().into_iter().collect()
+    }
+}
@@ -0,0 +1,158 @@
+//! A prefix tree map with string keys.
+
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug)]
+struct Node<V> {
+    value: Option<V>,
+    children: BTreeMap<u8, usize>,
+}
+
+impl<V> Node<V> {
+    fn new() -> Self {
+        Node { value: None, children: BTreeMap::new() }
+    }
+}
+
+/// A map with string keys stored in a prefix tree (trie).
+///
+/// In addition to exact lookups, it supports finding the longest key that
+/// is a prefix of a string, and iterating all keys with a given prefix.
+/// Iteration is in sorted key order.
+///
+/// Created with the [`triemap!`](macro.triemap.html) macro.
+#[derive(Clone, Debug)]
+pub struct TrieMap<V> {
+    nodes: Vec<Node<V>>,
+    len: usize,
+}
+
+impl<V> TrieMap<V> {
+    /// Create a new, empty map.
+    pub fn new() -> Self {
+        TrieMap { nodes: vec![Node::new()], len: 0 }
+    }
+
+    /// Return the number of entries.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return `true` if the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Insert a key-value pair, returning the old value if the key was
+    /// already present.
+    pub fn insert(&mut self, key: &str, value: V) -> Option<V> {
+        let mut node = 0;
+        for &byte in key.as_bytes() {
+            node = match self.nodes[node].children.get(&byte) {
+                Some(&child) => child,
+                None => {
+                    let child = self.nodes.len();
+                    self.nodes.push(Node::new());
+                    let _ = self.nodes[node].children.insert(byte, child);
+                    child
+                }
+            };
+        }
+        let old = self.nodes[node].value.replace(value);
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+
+    /// Return the node for `key`, if the trie has a path for it.
+    fn find(&self, key: &str) -> Option<usize> {
+        let mut node = 0;
+        for byte in key.bytes() {
+            node = *self.nodes[node].children.get(&byte)?;
+        }
+        Some(node)
+    }
+
+    /// Return a reference to the value for `key`, if present.
+    pub fn get(&self, key: &str) -> Option<&V> {
+        self.find(key).and_then(|node| self.nodes[node].value.as_ref())
+    }
+
+    /// Return `true` if the map contains `key`.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Return the longest key in the map that is a prefix of `s`, as a
+    /// slice of `s`, together with its value.
+    pub fn longest_prefix_match<'s>(&self, s: &'s str) -> Option<(&'s str, &V)> {
+        let mut node = 0;
+        let mut best = self.nodes[0].value.as_ref().map(|v| (0, v));
+        for (i, byte) in s.bytes().enumerate() {
+            node = match self.nodes[node].children.get(&byte) {
+                Some(&child) => child,
+                None => break,
+            };
+            if let Some(ref value) = self.nodes[node].value {
+                best = Some((i + 1, value));
+            }
+        }
+        best.map(|(end, value)| (&s[..end], value))
+    }
+
+    /// Return an iterator of the entries whose keys start with `prefix`, in
+    /// sorted key order.
+    pub fn iter_prefix(&self, prefix: &str) -> Iter<'_, V> {
+        let stack = match self.find(prefix) {
+            Some(node) => vec![(node, prefix.as_bytes().to_vec())],
+            None => Vec::new(),
+        };
+        Iter { trie: self, stack }
+    }
+
+    /// Return an iterator of all the entries, in sorted key order.
+    pub fn iter(&self) -> Iter<'_, V> {
+        self.iter_prefix("")
+    }
+}
+
+impl<V> Default for TrieMap<V> {
+    fn default() -> Self {
+        TrieMap::new()
+    }
+}
+
+impl<'a, V> IntoIterator for &'a TrieMap<V> {
+    type Item = (String, &'a V);
+    type IntoIter = Iter<'a, V>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the entries of a [`TrieMap`](struct.TrieMap.html).
+#[derive(Clone, Debug)]
+pub struct Iter<'a, V: 'a> {
+    trie: &'a TrieMap<V>,
+    stack: Vec<(usize, Vec<u8>)>,
+}
+
+impl<'a, V> Iterator for Iter<'a, V> {
+    type Item = (String, &'a V);
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((node, key)) = self.stack.pop() {
+            let node = &self.trie.nodes[node];
+            for (&byte, &child) in node.children.iter().rev() {
+                let mut child_key = key.clone();
+                child_key.push(byte);
+                self.stack.push((child, child_key));
+            }
+            if let Some(ref value) = node.value {
+                let key = String::from_utf8(key).expect("TrieMap: keys are valid UTF-8");
+                return Some((key, value));
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,66 @@
+//! A heterogeneous map keyed by type.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+
+/// A map that holds at most one value of each type, keyed by the type.
+///
+/// Useful for extension and state containers, where unrelated parts of a
+/// program each store their own data.
+///
+/// Created with the [`typemap!`](macro.typemap.html) macro.
+#[derive(Default)]
+pub struct TypeMap {
+    map: HashMap<TypeId, Box<dyn Any>>,
+}
+
+impl TypeMap {
+    /// Create a new, empty map.
+    pub fn new() -> Self {
+        TypeMap::default()
+    }
+
+    /// Return the number of entries.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Return `true` if the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Insert `value`, returning the old value of type `T` if there was one.
+    pub fn insert<T: Any>(&mut self, value: T) -> Option<T> {
+        self.map.insert(TypeId::of::<T>(), Box::new(value))
+            .map(|old| *old.downcast::<T>().expect("TypeMap: entry has the type of its key"))
+    }
+
+    /// Return a reference to the value of type `T`, if present.
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.map.get(&TypeId::of::<T>()).and_then(|value| value.downcast_ref())
+    }
+
+    /// Return a mutable reference to the value of type `T`, if present.
+    pub fn get_mut<T: Any>(&mut self) -> Option<&mut T> {
+        self.map.get_mut(&TypeId::of::<T>()).and_then(|value| value.downcast_mut())
+    }
+
+    /// Return `true` if the map has a value of type `T`.
+    pub fn contains<T: Any>(&self) -> bool {
+        self.map.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Remove the value of type `T` and return it, if present.
+    pub fn remove<T: Any>(&mut self) -> Option<T> {
+        self.map.remove(&TypeId::of::<T>())
+            .map(|old| *old.downcast::<T>().expect("TypeMap: entry has the type of its key"))
+    }
+}
+
+impl fmt::Debug for TypeMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TypeMap").field("len", &self.len()).finish()
+    }
+}
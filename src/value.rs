@@ -0,0 +1,158 @@
+//! A dynamically typed value, for untyped trees of maps and lists.
+
+use std::collections::HashMap;
+
+/// A dynamically typed value: null, a bool, a number, a string, a list of
+/// values or a map of values.
+///
+/// Trees of values are created with the
+/// [`value_map!`](../macro.value_map.html) macro, and values convert from
+/// the corresponding Rust types with `From`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    /// No value.
+    Null,
+    /// A bool.
+    Bool(bool),
+    /// An integer.
+    Int(i64),
+    /// A floating point number.
+    Float(f64),
+    /// A string.
+    Str(String),
+    /// A list of values.
+    List(Vec<Value>),
+    /// A map of values by string keys.
+    Map(HashMap<String, Value>),
+}
+
+impl Value {
+    /// Return `true` if this is `Null`.
+    pub fn is_null(&self) -> bool {
+        *self == Value::Null
+    }
+
+    /// Return the bool, if this is a bool.
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Value::Bool(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    /// Return the integer, if this is an integer.
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Value::Int(i) => Some(i),
+            _ => None,
+        }
+    }
+
+    /// Return the number as a float, if this is a float or an integer.
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Value::Int(i) => Some(i as f64),
+            Value::Float(f) => Some(f),
+            _ => None,
+        }
+    }
+
+    /// Return the string, if this is a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match *self {
+            Value::Str(ref s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Return the list, if this is a list.
+    pub fn as_list(&self) -> Option<&[Value]> {
+        match *self {
+            Value::List(ref list) => Some(list),
+            _ => None,
+        }
+    }
+
+    /// Return the map, if this is a map.
+    pub fn as_map(&self) -> Option<&HashMap<String, Value>> {
+        match *self {
+            Value::Map(ref map) => Some(map),
+            _ => None,
+        }
+    }
+
+    /// Return the value for `key`, if this is a map that contains it.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.as_map().and_then(|map| map.get(key))
+    }
+}
+
+/// The default value is `Null`.
+impl Default for Value {
+    fn default() -> Self {
+        Value::Null
+    }
+}
+
+macro_rules! from_int {
+    ($($t:ty)*) => {
+        $(
+            impl From<$t> for Value {
+                fn from(i: $t) -> Self {
+                    Value::Int(i64::from(i))
+                }
+            }
+        )*
+    };
+}
+
+from_int!(i8 i16 i32 i64 u8 u16 u32);
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+impl From<f32> for Value {
+    fn from(f: f32) -> Self {
+        Value::Float(f64::from(f))
+    }
+}
+
+impl From<f64> for Value {
+    fn from(f: f64) -> Self {
+        Value::Float(f)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::Str(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::Str(s)
+    }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(list: Vec<T>) -> Self {
+        Value::List(list.into_iter().map(Into::into).collect())
+    }
+}
+
+impl From<HashMap<String, Value>> for Value {
+    fn from(map: HashMap<String, Value>) -> Self {
+        Value::Map(map)
+    }
+}
+
+/// `None` becomes `Null`.
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(option: Option<T>) -> Self {
+        option.map_or(Value::Null, Into::into)
+    }
+}
@@ -1,5 +1,13 @@
+// `test_parse` spells out expressions like `0 + 2` on purpose.
+#![allow(clippy::identity_op)]
 
 #[macro_use] extern crate maplit;
+#[cfg(feature = "strum")]
+extern crate strum;
+#[cfg(feature = "enum-map")]
+#[macro_use] extern crate enum_map;
+#[cfg(feature = "http")]
+extern crate http;
 
 #[test]
 #[allow(unused_parens)]
@@ -63,3 +71,853 @@ fn hashset() {
     };
     assert_eq!(set.len(), 10);
 }
+
+#[cfg(feature = "smallvec")]
+#[test]
+fn alist() {
+    let mut map = alist!{"a" => 1, "b" => 2, "a" => 3};
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get("a"), Some(&3));
+    assert_eq!(map.insert("c", 4), None);
+    assert_eq!(map.insert("b", 5), Some(2));
+    let entries: Vec<_> = map.iter().map(|(&k, &v)| (k, v)).collect();
+    assert_eq!(entries, [("a", 3), ("b", 5), ("c", 4)]);
+}
+
+#[test]
+fn counter() {
+    let mut a = counter!{"x", "y", "x"};
+    let b = counter!{"x", "z"};
+    a += b;
+    assert_eq!(a.count("x"), 3);
+    assert_eq!(a.count("z"), 1);
+    assert_eq!(a.total(), 5);
+    a.subtract(&counter!{"x", "z", "z"});
+    assert_eq!(a.count("x"), 2);
+    assert!(!a.contains_key("z"));
+}
+
+#[test]
+fn cimap() {
+    let mut map = cimap!{"Accept" => 1, "ACCEPT" => 2, "Host" => 3};
+    assert_eq!(map.len(), 2);
+    assert_eq!(map["accept"], 2);
+    assert_eq!(map.iter().find(|e| *e.1 == 2).map(|e| e.0), Some("Accept"));
+    assert_eq!(map.remove("hOST"), Some(3));
+    assert!(!map.contains_key("host"));
+    // only ASCII is case folded
+    let map = cimap!{"Ä" => 1};
+    assert!(!map.contains_key("ä"));
+}
+
+#[test]
+#[allow(clippy::reversed_empty_ranges)]
+fn rangeset() {
+    let a = rangeset![0u8..10, 250..];
+    let b = rangeset![5u8..=252];
+    assert!(a.contains(&255));
+    assert!(!a.contains(&10));
+    assert_eq!(a.intersection(&b).iter().collect::<Vec<_>>(), [5..=9, 250..=252]);
+    assert_eq!(a.union(&b).iter().collect::<Vec<_>>(), [0..=255]);
+    assert!(rangeset![3..3, 5..=4].is_empty());
+}
+
+#[test]
+fn internedmap_across_threads() {
+    let map = internedmap!{"a" => 1, "b" => 2};
+    let handle = std::thread::spawn(move || map.get("b").cloned());
+    assert_eq!(handle.join().unwrap(), Some(2));
+}
+
+#[test]
+fn sortedvecmap() {
+    let mut map = sortedvecmap!{3 => "c", 1 => "a", 3 => "C", 2 => "b"};
+    assert_eq!(map.len(), 3);
+    assert_eq!(map.as_slice(), [(1, "a"), (2, "b"), (3, "C")]);
+    assert_eq!(map.insert(0, "z"), None);
+    assert_eq!(map.remove(&2), Some("b"));
+    assert_eq!(map.as_slice(), [(0, "z"), (1, "a"), (3, "C")]);
+}
+
+#[test]
+fn map_builder() {
+    use maplit::MapBuilder;
+
+    let builder = MapBuilder::new().entries((0..3).map(|i| (i, i * i))).entry(1, 0);
+    assert_eq!(builder.len(), 4);
+    let map = builder.clone().build_btreemap();
+    assert_eq!(map, btreemap!{0 => 0, 1 => 0, 2 => 4});
+    let map = builder.build_hashmap();
+    assert_eq!(map, hashmap!{0 => 0, 1 => 0, 2 => 4});
+}
+
+#[test]
+fn generic_literals() {
+    use std::collections::{BTreeMap, HashSet};
+
+    let map = map_of!(BTreeMap<_, _>; 2 => 'b', 1 => 'a', 2 => 'B');
+    assert_eq!(map, btreemap!{1 => 'a', 2 => 'B'});
+    let set = set_of!(HashSet<_>; "x", "y", "x",);
+    assert_eq!(set, hashset!{"x", "y"});
+    let empty = set_of!(Vec<i32>;);
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn insert_all() {
+    let mut map = btreemap!{1 => "a"};
+    insert_all!(map; @unique; 2 => "b", 3 => "c",);
+    insert_all!(&mut map; 1 => "A");
+    assert_eq!(map, btreemap!{1 => "A", 2 => "b", 3 => "c"});
+    let mut set = hashset!{};
+    add_all!(set; "x", "y",);
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn with() {
+    let base = btreemap!{1 => "a"};
+    let base_ref = &base;
+    let map = with!(base_ref; 2 => "b");
+    assert_eq!(map, btreemap!{1 => "a", 2 => "b"});
+    let map = with!(move map; @keep; 1 => "A", 3 => "c",);
+    assert_eq!(map, btreemap!{1 => "a", 2 => "b", 3 => "c"});
+    assert_eq!(base.len(), 1);
+}
+
+#[test]
+fn map_merge() {
+    let a = btreemap!{1 => "a", 2 => "b"};
+    let map = map_merge!(a, vec![(2, "B")], {3 => "c",},);
+    assert_eq!(map, btreemap!{1 => "a", 2 => "B", 3 => "c"});
+    let map = map_merge!({1 => 1, 2 => 2}, hashmap!{2 => 20}, {1 => 10});
+    assert_eq!(map, hashmap!{1 => 10, 2 => 20});
+}
+
+#[test]
+fn invert() {
+    let map = invert!(@last; vec![("a", 1), ("b", 1), ("c", 2)]);
+    assert_eq!(map, hashmap!{1 => "b", 2 => "c"});
+    assert_eq!(invert!(@result; btreemap!{1 => 'x', 2 => 'y'}), Ok(hashmap!{'x' => 1, 'y' => 2}));
+}
+
+#[test]
+#[should_panic(expected = "two keys have the same value")]
+fn invert_duplicate() {
+    let _ = invert!{"a" => 1, "b" => 1};
+}
+
+#[test]
+#[should_panic(expected = "duplicate key `2`")]
+fn insert_all_unique() {
+    let mut map = hashmap!{2 => ()};
+    insert_all!(map; @unique; 1 => (), 2 => ());
+}
+
+#[test]
+fn default_values() {
+    use std::collections::BTreeMap;
+
+    let map = hashmap!{"a" => vec![1], "b" => @default, "c" => @default};
+    assert_eq!(map["a"], [1]);
+    assert!(map["b"].is_empty() && map["c"].is_empty());
+    let map: BTreeMap<_, String> = btreemap!{
+        1 => @default, 2 => "b".into(), 3 => "c".into(), 4 => "d".into(), 5 => @default,
+    };
+    assert_eq!(map.values().collect::<Vec<_>>(), ["", "b", "c", "d", ""]);
+    let default = vec![2];
+    let map = hashmap!{"a" => default};
+    assert_eq!(map["a"], [2]);
+}
+
+/// Invoke `$m!` with 600 entries `i => 2 * i`, followed by `$extra`.
+macro_rules! six_hundred_entries {
+    ($m:ident $($extra:tt)*) => {
+        $m!{
+            0 => 0, 1 => 2, 2 => 4, 3 => 6, 4 => 8, 5 => 10, 6 => 12, 7 => 14, 8 => 16, 9 => 18,
+            10 => 20, 11 => 22, 12 => 24, 13 => 26, 14 => 28, 15 => 30, 16 => 32, 17 => 34, 18 => 36, 19 => 38,
+            20 => 40, 21 => 42, 22 => 44, 23 => 46, 24 => 48, 25 => 50, 26 => 52, 27 => 54, 28 => 56, 29 => 58,
+            30 => 60, 31 => 62, 32 => 64, 33 => 66, 34 => 68, 35 => 70, 36 => 72, 37 => 74, 38 => 76, 39 => 78,
+            40 => 80, 41 => 82, 42 => 84, 43 => 86, 44 => 88, 45 => 90, 46 => 92, 47 => 94, 48 => 96, 49 => 98,
+            50 => 100, 51 => 102, 52 => 104, 53 => 106, 54 => 108, 55 => 110, 56 => 112, 57 => 114, 58 => 116, 59 => 118,
+            60 => 120, 61 => 122, 62 => 124, 63 => 126, 64 => 128, 65 => 130, 66 => 132, 67 => 134, 68 => 136, 69 => 138,
+            70 => 140, 71 => 142, 72 => 144, 73 => 146, 74 => 148, 75 => 150, 76 => 152, 77 => 154, 78 => 156, 79 => 158,
+            80 => 160, 81 => 162, 82 => 164, 83 => 166, 84 => 168, 85 => 170, 86 => 172, 87 => 174, 88 => 176, 89 => 178,
+            90 => 180, 91 => 182, 92 => 184, 93 => 186, 94 => 188, 95 => 190, 96 => 192, 97 => 194, 98 => 196, 99 => 198,
+            100 => 200, 101 => 202, 102 => 204, 103 => 206, 104 => 208, 105 => 210, 106 => 212, 107 => 214, 108 => 216, 109 => 218,
+            110 => 220, 111 => 222, 112 => 224, 113 => 226, 114 => 228, 115 => 230, 116 => 232, 117 => 234, 118 => 236, 119 => 238,
+            120 => 240, 121 => 242, 122 => 244, 123 => 246, 124 => 248, 125 => 250, 126 => 252, 127 => 254, 128 => 256, 129 => 258,
+            130 => 260, 131 => 262, 132 => 264, 133 => 266, 134 => 268, 135 => 270, 136 => 272, 137 => 274, 138 => 276, 139 => 278,
+            140 => 280, 141 => 282, 142 => 284, 143 => 286, 144 => 288, 145 => 290, 146 => 292, 147 => 294, 148 => 296, 149 => 298,
+            150 => 300, 151 => 302, 152 => 304, 153 => 306, 154 => 308, 155 => 310, 156 => 312, 157 => 314, 158 => 316, 159 => 318,
+            160 => 320, 161 => 322, 162 => 324, 163 => 326, 164 => 328, 165 => 330, 166 => 332, 167 => 334, 168 => 336, 169 => 338,
+            170 => 340, 171 => 342, 172 => 344, 173 => 346, 174 => 348, 175 => 350, 176 => 352, 177 => 354, 178 => 356, 179 => 358,
+            180 => 360, 181 => 362, 182 => 364, 183 => 366, 184 => 368, 185 => 370, 186 => 372, 187 => 374, 188 => 376, 189 => 378,
+            190 => 380, 191 => 382, 192 => 384, 193 => 386, 194 => 388, 195 => 390, 196 => 392, 197 => 394, 198 => 396, 199 => 398,
+            200 => 400, 201 => 402, 202 => 404, 203 => 406, 204 => 408, 205 => 410, 206 => 412, 207 => 414, 208 => 416, 209 => 418,
+            210 => 420, 211 => 422, 212 => 424, 213 => 426, 214 => 428, 215 => 430, 216 => 432, 217 => 434, 218 => 436, 219 => 438,
+            220 => 440, 221 => 442, 222 => 444, 223 => 446, 224 => 448, 225 => 450, 226 => 452, 227 => 454, 228 => 456, 229 => 458,
+            230 => 460, 231 => 462, 232 => 464, 233 => 466, 234 => 468, 235 => 470, 236 => 472, 237 => 474, 238 => 476, 239 => 478,
+            240 => 480, 241 => 482, 242 => 484, 243 => 486, 244 => 488, 245 => 490, 246 => 492, 247 => 494, 248 => 496, 249 => 498,
+            250 => 500, 251 => 502, 252 => 504, 253 => 506, 254 => 508, 255 => 510, 256 => 512, 257 => 514, 258 => 516, 259 => 518,
+            260 => 520, 261 => 522, 262 => 524, 263 => 526, 264 => 528, 265 => 530, 266 => 532, 267 => 534, 268 => 536, 269 => 538,
+            270 => 540, 271 => 542, 272 => 544, 273 => 546, 274 => 548, 275 => 550, 276 => 552, 277 => 554, 278 => 556, 279 => 558,
+            280 => 560, 281 => 562, 282 => 564, 283 => 566, 284 => 568, 285 => 570, 286 => 572, 287 => 574, 288 => 576, 289 => 578,
+            290 => 580, 291 => 582, 292 => 584, 293 => 586, 294 => 588, 295 => 590, 296 => 592, 297 => 594, 298 => 596, 299 => 598,
+            300 => 600, 301 => 602, 302 => 604, 303 => 606, 304 => 608, 305 => 610, 306 => 612, 307 => 614, 308 => 616, 309 => 618,
+            310 => 620, 311 => 622, 312 => 624, 313 => 626, 314 => 628, 315 => 630, 316 => 632, 317 => 634, 318 => 636, 319 => 638,
+            320 => 640, 321 => 642, 322 => 644, 323 => 646, 324 => 648, 325 => 650, 326 => 652, 327 => 654, 328 => 656, 329 => 658,
+            330 => 660, 331 => 662, 332 => 664, 333 => 666, 334 => 668, 335 => 670, 336 => 672, 337 => 674, 338 => 676, 339 => 678,
+            340 => 680, 341 => 682, 342 => 684, 343 => 686, 344 => 688, 345 => 690, 346 => 692, 347 => 694, 348 => 696, 349 => 698,
+            350 => 700, 351 => 702, 352 => 704, 353 => 706, 354 => 708, 355 => 710, 356 => 712, 357 => 714, 358 => 716, 359 => 718,
+            360 => 720, 361 => 722, 362 => 724, 363 => 726, 364 => 728, 365 => 730, 366 => 732, 367 => 734, 368 => 736, 369 => 738,
+            370 => 740, 371 => 742, 372 => 744, 373 => 746, 374 => 748, 375 => 750, 376 => 752, 377 => 754, 378 => 756, 379 => 758,
+            380 => 760, 381 => 762, 382 => 764, 383 => 766, 384 => 768, 385 => 770, 386 => 772, 387 => 774, 388 => 776, 389 => 778,
+            390 => 780, 391 => 782, 392 => 784, 393 => 786, 394 => 788, 395 => 790, 396 => 792, 397 => 794, 398 => 796, 399 => 798,
+            400 => 800, 401 => 802, 402 => 804, 403 => 806, 404 => 808, 405 => 810, 406 => 812, 407 => 814, 408 => 816, 409 => 818,
+            410 => 820, 411 => 822, 412 => 824, 413 => 826, 414 => 828, 415 => 830, 416 => 832, 417 => 834, 418 => 836, 419 => 838,
+            420 => 840, 421 => 842, 422 => 844, 423 => 846, 424 => 848, 425 => 850, 426 => 852, 427 => 854, 428 => 856, 429 => 858,
+            430 => 860, 431 => 862, 432 => 864, 433 => 866, 434 => 868, 435 => 870, 436 => 872, 437 => 874, 438 => 876, 439 => 878,
+            440 => 880, 441 => 882, 442 => 884, 443 => 886, 444 => 888, 445 => 890, 446 => 892, 447 => 894, 448 => 896, 449 => 898,
+            450 => 900, 451 => 902, 452 => 904, 453 => 906, 454 => 908, 455 => 910, 456 => 912, 457 => 914, 458 => 916, 459 => 918,
+            460 => 920, 461 => 922, 462 => 924, 463 => 926, 464 => 928, 465 => 930, 466 => 932, 467 => 934, 468 => 936, 469 => 938,
+            470 => 940, 471 => 942, 472 => 944, 473 => 946, 474 => 948, 475 => 950, 476 => 952, 477 => 954, 478 => 956, 479 => 958,
+            480 => 960, 481 => 962, 482 => 964, 483 => 966, 484 => 968, 485 => 970, 486 => 972, 487 => 974, 488 => 976, 489 => 978,
+            490 => 980, 491 => 982, 492 => 984, 493 => 986, 494 => 988, 495 => 990, 496 => 992, 497 => 994, 498 => 996, 499 => 998,
+            500 => 1000, 501 => 1002, 502 => 1004, 503 => 1006, 504 => 1008, 505 => 1010, 506 => 1012, 507 => 1014, 508 => 1016, 509 => 1018,
+            510 => 1020, 511 => 1022, 512 => 1024, 513 => 1026, 514 => 1028, 515 => 1030, 516 => 1032, 517 => 1034, 518 => 1036, 519 => 1038,
+            520 => 1040, 521 => 1042, 522 => 1044, 523 => 1046, 524 => 1048, 525 => 1050, 526 => 1052, 527 => 1054, 528 => 1056, 529 => 1058,
+            530 => 1060, 531 => 1062, 532 => 1064, 533 => 1066, 534 => 1068, 535 => 1070, 536 => 1072, 537 => 1074, 538 => 1076, 539 => 1078,
+            540 => 1080, 541 => 1082, 542 => 1084, 543 => 1086, 544 => 1088, 545 => 1090, 546 => 1092, 547 => 1094, 548 => 1096, 549 => 1098,
+            550 => 1100, 551 => 1102, 552 => 1104, 553 => 1106, 554 => 1108, 555 => 1110, 556 => 1112, 557 => 1114, 558 => 1116, 559 => 1118,
+            560 => 1120, 561 => 1122, 562 => 1124, 563 => 1126, 564 => 1128, 565 => 1130, 566 => 1132, 567 => 1134, 568 => 1136, 569 => 1138,
+            570 => 1140, 571 => 1142, 572 => 1144, 573 => 1146, 574 => 1148, 575 => 1150, 576 => 1152, 577 => 1154, 578 => 1156, 579 => 1158,
+            580 => 1160, 581 => 1162, 582 => 1164, 583 => 1166, 584 => 1168, 585 => 1170, 586 => 1172, 587 => 1174, 588 => 1176, 589 => 1178,
+            590 => 1180, 591 => 1182, 592 => 1184, 593 => 1186, 594 => 1188, 595 => 1190, 596 => 1192, 597 => 1194, 598 => 1196, 599 => 1198,
+            $($extra)*
+        }
+    };
+}
+
+#[test]
+fn token_count() {
+    assert_eq!(__count!(), 0);
+    assert_eq!(__count!(a), 1);
+    assert_eq!(__count!(a b c d e f g), 7);
+    assert_eq!(__count!(a (b c) [d] e f g h i), 8);
+}
+
+#[test]
+fn large_literals() {
+    let map = six_hundred_entries!(hashmap);
+    assert_eq!(map.len(), 600);
+    assert_eq!(map[&599], 1198);
+    let map = six_hundred_entries!(btreemap);
+    assert_eq!(map.len(), 600);
+    assert_eq!(map[&599], 1198);
+
+    // The entries are inserted in several blocks, where `?` still returns
+    // from the enclosing function.
+    fn build(last: Option<i32>) -> Option<std::collections::BTreeMap<i32, i32>> {
+        Some(six_hundred_entries!(btreemap 600 => last?))
+    }
+    assert_eq!(build(Some(0)).unwrap()[&600], 0);
+    assert_eq!(build(None), None);
+}
+
+#[test]
+fn value_modifiers() {
+    use std::cell::{Cell, RefCell};
+    use std::collections::{BTreeMap, HashMap};
+    use std::rc::Rc;
+    use std::sync::{Arc, Mutex, RwLock};
+
+    let map: HashMap<_, Rc<i32>> = hashmap!{@rc; 1 => 1, 2 => 2,};
+    assert_eq!(*map[&2], 2);
+    let map: BTreeMap<_, Arc<&str>> = btreemap!{@arc; 1 => "a"};
+    assert_eq!(*map[&1], "a");
+    let map: HashMap<_, RefCell<Vec<i32>>> = hashmap!{@refcell; "a" => vec![]};
+    map["a"].borrow_mut().push(1);
+    assert_eq!(*map["a"].borrow(), [1]);
+    let map: BTreeMap<_, Cell<u8>> = btreemap!{@cell; 'a' => 1};
+    map[&'a'].set(2);
+    assert_eq!(map[&'a'].get(), 2);
+    let map: HashMap<_, Mutex<u8>> = hashmap!{@mutex; 1 => 0};
+    *map[&1].lock().unwrap() += 1;
+    assert_eq!(*map[&1].lock().unwrap(), 1);
+    let map: BTreeMap<_, RwLock<u8>> = btreemap!{@rwlock; 1 => 5};
+    assert_eq!(*map[&1].read().unwrap(), 5);
+}
+
+#[test]
+fn from_iter_expansion() {
+    use std::collections::{BTreeMap, HashMap};
+
+    let map = hashmap!{@from_iter; "a" => 1, "b" => 2, "a" => 3,};
+    assert_eq!(map, hashmap!{"a" => 3, "b" => 2});
+    let map = btreemap!{@from_iter; 2 => 'b', 1 => 'a', 2 => 'c'};
+    assert_eq!(map, btreemap!{1 => 'a', 2 => 'c'});
+    let empty: HashMap<u8, u8> = hashmap!{@from_iter;};
+    assert!(empty.is_empty());
+    let empty: BTreeMap<u8, u8> = btreemap!{@from_iter;};
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn from_array_expansion() {
+    use std::collections::HashSet;
+
+    let map = hashmap!{@from_array; "a" => 1, "b" => 2, "a" => 3,};
+    assert_eq!(map, hashmap!{"a" => 3, "b" => 2});
+    let set = hashset!{@from_array; 1, 2, 1};
+    assert_eq!(set, hashset!{1, 2});
+    let empty: HashSet<u8> = hashset!{@from_array;};
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn reserve() {
+    use std::collections::HashMap;
+
+    let map = hashmap!{@reserve 50; 1 => 'a', 2 => 'b',};
+    assert_eq!(map, hashmap!{1 => 'a', 2 => 'b'});
+    assert!(map.capacity() >= 52);
+    let extra = 10;
+    let empty: HashMap<u8, u8> = hashmap!{@reserve extra * 2;};
+    assert!(empty.capacity() >= 20);
+}
+
+#[test]
+fn btreemap_sorted() {
+    let map = btreemap!{@sorted; 1 => 'a', 2 => 'b', 5 => 'c',};
+    assert_eq!(map, btreemap!{5 => 'c', 1 => 'a', 2 => 'b'});
+    let empty: std::collections::BTreeMap<u8, u8> = btreemap!{@sorted;};
+    assert!(empty.is_empty());
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "@sorted keys are not in increasing order")]
+fn btreemap_sorted_unsorted() {
+    let _ = btreemap!{@sorted; "b" => 1, "a" => 2};
+}
+
+#[test]
+fn convert_args_casts() {
+    use std::collections::{BTreeMap, HashSet};
+
+    let map: BTreeMap<u64, &str> = convert_args!(keys = as u64, btreemap!(1u32 => "a", 2u32 => "b"));
+    assert_eq!(map[&2], "b");
+    let map: BTreeMap<String, f64> = convert_args!(keys=String::from, values = as f64, btreemap!(
+        "x" => -1i64,
+    ));
+    assert_eq!(map["x"], -1.);
+    let map: BTreeMap<i32, u8> = convert_args!(values = as u8, btreemap!(1 => 'a'));
+    assert_eq!(map[&1], b'a');
+    let set: HashSet<i64> = convert_args!(keys = as i64, hashset!(1u8, 2u8));
+    assert!(set.contains(&2));
+}
+
+#[cfg(feature = "proc-macro")]
+mod exhaustive {
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    pub enum Level { Low, High }
+}
+
+#[cfg(feature = "proc-macro")]
+#[test]
+fn exhaustive_map() {
+    let map = exhaustive_map!(exhaustive::Level; High => 10, Low => 1,);
+    assert_eq!(map.len(), 2);
+    assert_eq!(map[&exhaustive::Level::High], 10);
+}
+
+#[cfg(feature = "proc-macro")]
+#[test]
+fn checked_maps() {
+    let map = maplit::checked::hashmap!{"a" => 1, "b" => @default};
+    assert_eq!(map, hashmap!{"a" => 1, "b" => 0});
+    let empty: std::collections::BTreeMap<i32, i32> = maplit::checked::btreemap!{};
+    assert!(empty.is_empty());
+    let map = maplit::checked::btreemap!{2 => vec![1], 1 => vec![],};
+    assert_eq!(map, btreemap!{1 => vec![], 2 => vec![1]});
+}
+
+#[cfg(feature = "proc-macro")]
+#[test]
+fn checked_maps_chunked() {
+    // `?` in a value returns from this function, not from a chunk.
+    fn build(last: Option<i32>) -> Option<std::collections::HashMap<i32, i32>> {
+        Some(maplit::checked::hashmap!{
+            0 => 0, 1 => 2, 2 => 4, 3 => 6, 4 => 8, 5 => 10, 6 => 12, 7 => 14, 8 => 16, 9 => 18,
+            10 => 20, 11 => 22, 12 => 24, 13 => 26, 14 => 28, 15 => 30, 16 => 32, 17 => 34, 18 => 36, 19 => 38,
+            20 => 40, 21 => 42, 22 => 44, 23 => 46, 24 => 48, 25 => 50, 26 => 52, 27 => 54, 28 => 56, 29 => 58,
+            30 => 60, 31 => 62, 32 => 64, 33 => 66, 34 => 68, 35 => 70, 36 => 72, 37 => 74, 38 => 76, 39 => 78,
+            40 => 80, 41 => 82, 42 => 84, 43 => 86, 44 => 88, 45 => 90, 46 => 92, 47 => 94, 48 => 96, 49 => 98,
+            50 => 100, 51 => 102, 52 => 104, 53 => 106, 54 => 108, 55 => 110, 56 => 112, 57 => 114, 58 => 116, 59 => 118,
+            60 => 120, 61 => 122, 62 => 124, 63 => 126, 64 => 128, 65 => 130, 66 => 132, 67 => 134, 68 => 136, 69 => 138,
+            70 => 140, 71 => 142, 72 => 144, 73 => 146, 74 => 148, 75 => 150, 76 => 152, 77 => 154, 78 => 156, 79 => 158,
+            80 => 160, 81 => 162, 82 => 164, 83 => 166, 84 => 168, 85 => 170, 86 => 172, 87 => 174, 88 => 176, 89 => 178,
+            90 => 180, 91 => 182, 92 => 184, 93 => 186, 94 => 188, 95 => 190, 96 => 192, 97 => 194, 98 => 196, 99 => 198,
+            100 => 200, 101 => 202, 102 => 204, 103 => 206, 104 => 208, 105 => 210, 106 => 212, 107 => 214, 108 => 216, 109 => 218,
+            110 => 220, 111 => 222, 112 => 224, 113 => 226, 114 => 228, 115 => 230, 116 => 232, 117 => 234, 118 => 236, 119 => 238,
+            120 => 240, 121 => 242, 122 => 244, 123 => 246, 124 => 248, 125 => 250, 126 => 252, 127 => 254, 128 => 256, 129 => 258,
+            130 => 260, 131 => 262, 132 => 264, 133 => 266, 134 => 268, 135 => 270, 136 => 272, 137 => 274, 138 => 276, 139 => 278,
+            140 => 280, 141 => 282, 142 => 284, 143 => 286, 144 => 288, 145 => 290, 146 => 292, 147 => 294, 148 => 296, 149 => 298,
+            150 => 300, 151 => 302, 152 => 304, 153 => 306, 154 => 308, 155 => 310, 156 => 312, 157 => 314, 158 => 316, 159 => 318,
+            160 => 320, 161 => 322, 162 => 324, 163 => 326, 164 => 328, 165 => 330, 166 => 332, 167 => 334, 168 => 336, 169 => 338,
+            170 => 340, 171 => 342, 172 => 344, 173 => 346, 174 => 348, 175 => 350, 176 => 352, 177 => 354, 178 => 356, 179 => 358,
+            180 => 360, 181 => 362, 182 => 364, 183 => 366, 184 => 368, 185 => 370, 186 => 372, 187 => 374, 188 => 376, 189 => 378,
+            190 => 380, 191 => 382, 192 => 384, 193 => 386, 194 => 388, 195 => 390, 196 => 392, 197 => 394, 198 => 396, 199 => 398,
+            200 => 400, 201 => 402, 202 => 404, 203 => 406, 204 => 408, 205 => 410, 206 => 412, 207 => 414, 208 => 416, 209 => 418,
+            210 => 420, 211 => 422, 212 => 424, 213 => 426, 214 => 428, 215 => 430, 216 => 432, 217 => 434, 218 => 436, 219 => 438,
+            220 => 440, 221 => 442, 222 => 444, 223 => 446, 224 => 448, 225 => 450, 226 => 452, 227 => 454, 228 => 456, 229 => 458,
+            230 => 460, 231 => 462, 232 => 464, 233 => 466, 234 => 468, 235 => 470, 236 => 472, 237 => 474, 238 => 476, 239 => 478,
+            240 => 480, 241 => 482, 242 => 484, 243 => 486, 244 => 488, 245 => 490, 246 => 492, 247 => 494, 248 => 496, 249 => 498,
+            250 => 500, 251 => 502, 252 => 504, 253 => 506, 254 => 508, 255 => 510, 256 => 512, 257 => 514, 258 => 516, 259 => 518,
+            260 => 520, 261 => 522, 262 => 524, 263 => 526, 264 => 528, 265 => 530, 266 => 532, 267 => 534, 268 => 536, 269 => 538,
+            270 => 540, 271 => 542, 272 => 544, 273 => 546, 274 => 548, 275 => 550, 276 => 552, 277 => 554, 278 => 556, 279 => 558,
+            280 => 560, 281 => 562, 282 => 564, 283 => 566, 284 => 568, 285 => 570, 286 => 572, 287 => 574, 288 => 576, 289 => 578,
+            290 => 580, 291 => 582, 292 => 584, 293 => 586, 294 => 588, 295 => 590, 296 => 592, 297 => 594, 298 => 596, 299 => 598,
+            300 => last?,
+        })
+    }
+    let map = build(Some(1000)).unwrap();
+    assert_eq!(map.len(), 301);
+    assert_eq!(map[&299], 598);
+    assert_eq!(map[&300], 1000);
+    assert_eq!(build(None), None);
+}
+
+#[cfg(feature = "prehashed")]
+#[test]
+fn prehashed_map() {
+    let map = prehashed_map!{"a" => 1, "b" => @default, "a" => 3};
+    assert_eq!(map.len(), 2);
+    assert_eq!(map["a"], 3);
+    assert_eq!(map["b"], 0);
+    let map = prehashed_map!{'x' => 1, '\u{1F600}' => 2};
+    assert_eq!(map[&'\u{1F600}'], 2);
+    let map = prehashed_map!{true => "yes", false => "no"};
+    assert_eq!(map[&false], "no");
+    let map = prehashed_map!{255u8 => 0, 7u8 => 1};
+    assert_eq!(map[&7], 1);
+    let map = prehashed_map!{7usize => 1};
+    assert_eq!(map[&7], 1);
+    let map = prehashed_map!{-3isize => 1};
+    assert_eq!(map[&-3], 1);
+    let map = prehashed_map!{-170141183460469231731687303715884105728i128 => 'a', 3i128 => 'b'};
+    assert_eq!(map[&i128::MIN], 'a');
+}
+
+#[cfg(feature = "proc-macro")]
+static PERFECT: maplit::PerfectMap<u32, usize> = perfectmap!{
+    0u32 => 0, 7u32 => 1, 14u32 => 2, 21u32 => 3, 28u32 => 4, 35u32 => 5, 42u32 => 6, 49u32 => 7,
+    56u32 => 8, 63u32 => 9, 70u32 => 10, 77u32 => 11, 84u32 => 12, 91u32 => 13, 98u32 => 14, 105u32 => 15,
+    112u32 => 16, 119u32 => 17, 126u32 => 18, 133u32 => 19, 140u32 => 20, 147u32 => 21, 154u32 => 22, 161u32 => 23,
+    168u32 => 24, 175u32 => 25, 182u32 => 26, 189u32 => 27, 196u32 => 28, 203u32 => 29, 210u32 => 30, 217u32 => 31,
+    224u32 => 32, 231u32 => 33, 238u32 => 34, 245u32 => 35, 252u32 => 36, 259u32 => 37, 266u32 => 38, 273u32 => 39,
+};
+
+#[cfg(feature = "proc-macro")]
+#[test]
+fn perfectmap() {
+    assert_eq!(PERFECT.len(), 40);
+    for i in 0..40 {
+        assert_eq!(PERFECT.get(&(i as u32 * 7)), Some(&i));
+        assert!(!PERFECT.contains_key(&(i as u32 * 7 + 1)));
+    }
+    let empty: maplit::PerfectMap<char, ()> = perfectmap!{};
+    assert_eq!(empty.get(&'a'), None);
+}
+
+#[cfg(feature = "proc-macro")]
+#[test]
+fn include_map() {
+    let mime = include_map!("tests/data/mime.properties");
+    assert_eq!(mime, hashmap!{
+        "html" => "text/html",
+        "css" => "text/css",
+        "png" => "image/png",
+        "json" => "application/json",
+    });
+}
+
+#[cfg(feature = "proc-macro")]
+#[test]
+fn include_set() {
+    let words = include_set!("tests/data/stopwords.txt",);
+    assert_eq!(words, hashset!{"a", "an", "the", "of"});
+    let words = btree_include_set!("tests/data/stopwords.txt");
+    assert_eq!(words.into_iter().collect::<Vec<_>>(), ["a", "an", "of", "the"]);
+}
+
+#[cfg(feature = "proc-macro")]
+#[test]
+fn env_map() {
+    let package = env_map!(prefix = "CARGO_PKG_NA");
+    assert_eq!(package, hashmap!{"CARGO_PKG_NAME" => "maplit"});
+    let empty = env_map!(prefix = "MAPLIT_TEST_UNSET_");
+    assert!(empty.is_empty());
+    let vars = env_map!{"CARGO_PKG_NAME", "MAPLIT_TEST_UNSET" => "fallback",};
+    assert_eq!(vars["CARGO_PKG_NAME"], "maplit");
+    assert_eq!(vars["MAPLIT_TEST_UNSET"], "fallback");
+}
+
+#[cfg(feature = "proc-macro")]
+#[test]
+fn querymap() {
+    let query = querymap!("a=1&&b&a=%E2%9C%93&c=x%2By",);
+    assert_eq!(query.len(), 3);
+    assert_eq!(query["a"], ["1", "\u{2713}"]);
+    assert_eq!(query["b"], [""]);
+    assert_eq!(query["c"], ["x+y"]);
+    let empty = querymap!(@single; "");
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn dispatch_table() {
+    fn first(args: &[i32]) -> Option<i32> { args.first().cloned() }
+    fn last(args: &[i32]) -> Option<i32> { args.last().cloned() }
+
+    let table = dispatch_table!(fn(&[i32]) -> Option<i32>;
+        "first" => first,
+        "last" => last,
+        "sum" => |args| Some(args.iter().sum())
+    );
+    let args = [1, 2, 3];
+    assert_eq!(table["first"](&args), Some(1));
+    assert_eq!(table["last"](&args), Some(3));
+    assert_eq!(table["sum"](&args), Some(6));
+
+    let base = 10;
+    let table = dispatch_table!(@boxed dyn Fn(i32) -> i32;
+        1 => move |x| x + base,
+        2 => first_arg,
+    );
+    fn first_arg(x: i32) -> i32 { x }
+    assert_eq!(table[&1](5), 15);
+    assert_eq!(table[&2](5), 5);
+}
+
+#[test]
+fn value_map() {
+    use maplit::Value;
+
+    let hosts = vec!["a", "b"];
+    let tree = value_map!{
+        "name" => "maplit",
+        "version" => 1,
+        "ratio" => 0.5,
+        "nested" => {"empty" => {}, "list" => [1, [true, null], {"x" => -2}]},
+        "hosts" => hosts,
+        "none" => None::<i32>
+    };
+    assert_eq!(tree.len(), 6);
+    assert_eq!(tree["name"].as_str(), Some("maplit"));
+    assert_eq!(tree["version"].as_f64(), Some(1.0));
+    assert_eq!(tree["ratio"], Value::Float(0.5));
+    assert_eq!(tree["nested"].get("empty"), Some(&Value::Map(Default::default())));
+    let list = tree["nested"].get("list").and_then(Value::as_list).unwrap();
+    assert_eq!(list[1], Value::List(vec![Value::Bool(true), Value::Null]));
+    assert_eq!(list[2].get("x").and_then(Value::as_i64), Some(-2));
+    assert_eq!(tree["hosts"], Value::from(vec!["a", "b"]));
+    assert!(tree["none"].is_null());
+    assert!(value_map!{}.is_empty());
+}
+
+#[cfg(feature = "proc-macro")]
+#[test]
+fn config() {
+    std::env::set_var("MAPLIT_TEST_CONFIG_PORT", "9000");
+    std::env::set_var("MAPLIT_TEST_CONFIG_DEBUG", "true");
+    let config = config!{
+        prefix = "MAPLIT_TEST_CONFIG_";
+        "port": u16 => 8080,
+        "host": String => "localhost",
+        "debug": bool => false,
+        "ratio": f64 => 0.5
+    };
+    assert_eq!(config.port(), 9000);
+    assert_eq!(config.host(), "localhost");
+    assert!(config.debug());
+    assert_eq!(config.ratio(), 0.5);
+    assert_eq!(config.to_map(), hashmap!{
+        "port" => "9000".to_string(),
+        "host" => "localhost".to_string(),
+        "debug" => "true".to_string(),
+        "ratio" => "0.5".to_string(),
+    });
+    let config = config!{"maplit_test_config_level": u8 => 3,};
+    assert_eq!(config.maplit_test_config_level(), 3);
+}
+
+#[cfg(feature = "proc-macro")]
+#[test]
+#[should_panic(expected = "config!: invalid value \"x\" for `MAPLIT_TEST_CONFIG_BAD`")]
+fn config_invalid() {
+    std::env::set_var("MAPLIT_TEST_CONFIG_BAD", "x");
+    let config = config!{"maplit_test_config_bad": u32 => 1};
+    let _ = config.maplit_test_config_bad();
+}
+
+#[cfg(feature = "proc-macro")]
+#[test]
+fn derive_to_map() {
+    use maplit::{ToMap, Value};
+
+    #[derive(ToMap)]
+    struct Entry<T> {
+        id: T,
+        #[map(rename = "label")]
+        name: &'static str,
+        tags: Vec<String>,
+        #[map(skip)]
+        _cache: std::cell::Cell<u8>,
+        parent: Option<u32>,
+    }
+
+    let entry = Entry { id: 7u8, name: "x", tags: vec!["a".to_string()],
+                        _cache: Default::default(), parent: None };
+    assert_eq!(entry.to_map(), hashmap!{
+        "id" => Value::Int(7),
+        "label" => Value::from("x"),
+        "tags" => Value::List(vec![Value::from("a")]),
+        "parent" => Value::Null,
+    });
+    let keys: Vec<_> = entry.to_btree_map().into_keys().collect();
+    assert_eq!(keys, ["id", "label", "parent", "tags"]);
+}
+
+#[cfg(feature = "proc-macro")]
+#[test]
+fn derive_from_map() {
+    use maplit::{FromMap, FromMapError};
+
+    #[derive(FromMap, Debug, PartialEq)]
+    struct Entry<T> {
+        id: T,
+        #[map(rename = "label", default = "none".to_string())]
+        name: String,
+        #[map(default)]
+        count: usize,
+        parent: Option<u32>,
+        #[map(skip)]
+        cache: Vec<u8>,
+    }
+
+    let entry = Entry::<u8>::from_map(&hashmap!{"id" => "7", "parent" => "1", "cache" => "x"});
+    assert_eq!(entry, Ok(Entry { id: 7, name: "none".to_string(), count: 0,
+                                 parent: Some(1), cache: vec![] }));
+    let map = btreemap!{"id".to_string() => "-1".to_string(), "label".to_string() => "x".to_string()};
+    let entry = Entry::<i64>::from_map(&map).unwrap();
+    assert_eq!((entry.id, &*entry.name, entry.parent), (-1, "x", None));
+    let err = Entry::<u8>::from_map(&hashmap!{"id" => "1", "count" => "many"}).unwrap_err();
+    assert_eq!(err.to_string(), "invalid value \"many\" for field `count`: invalid digit found in string");
+    assert_eq!(Entry::<u8>::from_map(&hashmap!{"label" => "x"}), Err(FromMapError::MissingField("id")));
+}
+
+#[cfg(feature = "strum")]
+#[test]
+fn map_from_variants() {
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, strum::EnumIter)]
+    enum Level { Low, Mid, High }
+
+    let mut calls = 0;
+    let map = map_from_variants!(Level, |&level| {
+        calls += 1;
+        level as u8 * 10
+    },);
+    assert_eq!(calls, 3);
+    assert_eq!(map, hashmap!{Level::Low => 0, Level::Mid => 10, Level::High => 20});
+}
+
+#[cfg(feature = "proc-macro")]
+#[test]
+fn dict() {
+    use maplit::Value;
+
+    let port = 80;
+    let tree = dict!{
+        name => "maplit",
+        r#type => "crate",
+        server { port => port, tls {}, },
+        "a b" => vec![1, 2]
+    };
+    assert_eq!(tree, value_map!{
+        "name" => "maplit",
+        "type" => "crate",
+        "server" => {"port" => 80, "tls" => {}},
+        "a b" => [1, 2],
+    });
+    assert_eq!(dict!{}, std::collections::HashMap::<String, Value>::new());
+}
+
+#[cfg(feature = "proc-macro")]
+#[test]
+fn checked_maps_newlines() {
+    let map = maplit::checked::hashmap!{
+        "a" => 1
+        "b" => vec![2]
+            .len()
+        "c" => 3,
+        "d" => 4
+        , "e" => 5
+    };
+    assert_eq!(map, hashmap!{"a" => 1, "b" => 1, "c" => 3, "d" => 4, "e" => 5});
+    let tree = dict!{
+        a => 1
+        b { c => 2
+            d => 3 }
+    };
+    assert_eq!(tree, value_map!{"a" => 1, "b" => {"c" => 2, "d" => 3}});
+}
+
+#[cfg(feature = "strum")]
+#[test]
+fn hashmap_fill_variants() {
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, strum::EnumIter)]
+    enum Level { Low, Mid, High }
+
+    let mut fills = 0;
+    let map = hashmap!{
+        Level::Mid => "mid".to_string(),
+        ..{ fills += 1; "other".to_string() },
+    };
+    assert_eq!(fills, 2);
+    assert_eq!(map[&Level::Mid], "mid");
+    assert_eq!(map[&Level::High], "other");
+    let map = hashmap!{Level::Low => 1, Level::Mid => @default, ..7};
+    assert_eq!(map, hashmap!{Level::Low => 1, Level::Mid => 0, Level::High => 7});
+    let map: std::collections::HashMap<Level, u8> = hashmap!{..Default::default()};
+    assert_eq!(map.len(), 3);
+    assert_eq!(hashmap!{..0 => 'a'}[&(..0)], 'a');
+}
+
+#[test]
+fn hashset_generated() {
+    let mut calls = 0;
+    let set = hashset![{ calls += 1; calls % 3 }; 10];
+    assert_eq!(calls, 10);
+    assert_eq!(set, hashset!{0, 1, 2});
+    assert!(set.capacity() >= 10);
+    let set: std::collections::HashSet<u8> = hashset![1; 0];
+    assert!(set.is_empty());
+    let set = hashset![format!("{}{}", k, v); for (k, v) in vec![("a", 1), ("b", 2)]];
+    assert_eq!(set, hashset!{"a1".to_string(), "b2".to_string()});
+    let set = hashset![i; for i in 0..50];
+    assert!(set.capacity() >= 50);
+}
+
+#[cfg(feature = "proc-macro")]
+#[test]
+fn include_map_csv() {
+    let names = include_map!(csv "tests/data/countries.csv", key = "code", value = "name",);
+    assert_eq!(names.len(), 4);
+    assert_eq!(names["fr"], "France");
+    let eu = include_map!(csv "tests/data/countries.csv", key = "name", value = ("eu" as bool));
+    assert_eq!(eu["Norway"], (false,));
+    let scores = include_map!(tsv "tests/data/scores.tsv", key = "id" as u8, value = "score" as f64);
+    assert_eq!(scores, hashmap!{1 => -0.5, 2 => 3.25});
+}
+
+#[test]
+fn assert_map_eq_passes() {
+    assert_map_eq!(hashmap!{1 => "a"}, convert_args!(keys = as i32, hashmap!(1u8 => "a")));
+}
+
+#[test]
+#[should_panic(expected = "maps differ)\n  different values for 2: left 'b', right 'c'\n  \
+                           only in right: 3 => 'c': context")]
+fn assert_map_eq_fails() {
+    assert_map_eq!(btreemap!{1 => 'a', 2 => 'b'}, btreemap!{1 => 'a', 2 => 'c', 3 => 'c'},
+                   "{}", "context");
+}
+
+#[test]
+#[should_panic(expected = "sets differ)\n  only in left: [\"a\", \"b\"]\n  only in right: [\"d\"]")]
+fn assert_set_eq_fails() {
+    assert_set_eq!(hashset!{"b", "c", "a"}, hashset!{"c", "d"});
+}
+
+#[test]
+#[should_panic(expected = "expected entries\n  missing key: \"b\"\n  different value for \"c\": map 3, expected 4")]
+fn assert_submap_fails() {
+    let map = hashmap!{"a".to_string() => 1, "c".to_string() => 3};
+    assert_submap!(map, {"a" => 1, "b" => 2, "c" => 4});
+}
+
+#[test]
+#[should_panic(expected = "doesn't contain the keys [2, 3]")]
+fn assert_contains_keys_fails() {
+    assert_contains_keys!(btreemap!{1 => ()}, &1, &2, &3);
+}
+
+#[test]
+fn sorted_debug() {
+    use maplit::SortedDebug;
+
+    let set = hashset!{3, 1, 2};
+    assert_eq!(format!("{:?}", SortedDebug(&set)), "{1, 2, 3}");
+    let map = hashmap!{2 => 'b', 1 => 'a'};
+    let map_ref = dbg_map!(&map);
+    assert_eq!(format!("{:#?}", SortedDebug(&map_ref)), "{\n    1: 'a',\n    2: 'b',\n}");
+}
+
+#[cfg(feature = "enum-map")]
+#[test]
+fn enummap_values_once() {
+    #[derive(Debug, Enum)]
+    enum Level { Low, High }
+
+    let mut order = Vec::new();
+    let name = String::from("high");
+    let map = enummap!{
+        Level::High => { order.push("high"); name },
+        Level::Low => { order.push("low"); String::new() },
+    };
+    assert_eq!(order, ["high", "low"]);
+    assert_eq!(map[Level::High], "high");
+    assert_eq!(map[Level::Low], "");
+}
+
+#[test]
+fn cstringmap_in_caller() {
+    use std::ffi::CString;
+
+    fn build(home: Option<&str>) -> Option<std::collections::HashMap<CString, CString>> {
+        cstringmap!{"LANG" => "C", "HOME" => home?}.ok()
+    }
+    assert_eq!(build(Some("/root")).unwrap().len(), 2);
+    assert_eq!(build(None), None);
+    assert!(cstringmap!{}.unwrap().is_empty());
+}
+
+#[test]
+fn submap_in_caller() {
+    use std::collections::HashMap;
+
+    fn pick(map: &HashMap<&'static str, i32>, key: Result<&'static str, String>)
+        -> Result<Result<HashMap<&'static str, i32>, &'static str>, String>
+    {
+        Ok(submap!(*map; @result; "a", key?))
+    }
+
+    let map = hashmap!{"a" => 1, "b" => 2};
+    assert_eq!(pick(&map, Ok("b")), Ok(Ok(map.clone())));
+    assert_eq!(pick(&map, Ok("c")), Ok(Err("c")));
+    assert_eq!(pick(&map, Err("bad".to_string())), Err("bad".to_string()));
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn headermap_in_caller() {
+    fn build(token: Option<&str>) -> Option<http::HeaderMap> {
+        headermap!{"authorization" => token?}.ok()
+    }
+
+    assert_eq!(build(Some("x")).unwrap()["authorization"], "x");
+    assert_eq!(build(None), None);
+    assert!(headermap!{}.unwrap().is_empty());
+}
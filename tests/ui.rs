@@ -0,0 +1,12 @@
+//! Compile the error cases of the procedural macros and compare their
+//! messages with the `.stderr` files in `tests/ui`.
+
+#![cfg(feature = "proc-macro")]
+
+extern crate trybuild;
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
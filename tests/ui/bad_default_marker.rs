@@ -0,0 +1,6 @@
+extern crate maplit;
+use maplit::checked::hashmap;
+
+fn main() {
+    let _map = hashmap!{"a" => @defualt};
+}
@@ -0,0 +1,9 @@
+extern crate maplit;
+use maplit::checked::btreemap;
+
+fn main() {
+    let _map = btreemap!{
+        "a" => 1,
+        "b" 2,
+    };
+}
@@ -0,0 +1,9 @@
+extern crate maplit;
+use maplit::checked::hashmap;
+
+fn main() {
+    let _map = hashmap!{
+        "a" => 1,
+        "b" =>
+    };
+}
@@ -0,0 +1,12 @@
+// A line break ends an entry only if the next line can't continue the
+// value: here `2 (3)` is parsed as a call, and the `=>` after it is
+// reported as a second `=>` in the first entry.
+extern crate maplit;
+use maplit::checked::hashmap;
+
+fn main() {
+    let _map = hashmap!{
+        1 => 2
+        (3) => 4
+    };
+}
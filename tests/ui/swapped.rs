@@ -0,0 +1,10 @@
+extern crate maplit;
+use maplit::checked::hashmap;
+
+fn main() {
+    let _map = hashmap!{
+        "a" => 1,
+        "b" => 2,
+        3 => "c",
+    };
+}